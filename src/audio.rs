@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+// The countdown ticks once a second over the last this many seconds of a game.
+pub const COUNTDOWN_TICKS: usize = 10;
+
+// Background music, as files inside the assets folder, e.g. "music/menu.ogg". None is silence.
+// No music ships with the game, it's opt-in by pointing these at your own files.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MusicSettings {
+    pub menu: Option<String>,
+    pub game: Option<String>,
+}
+
+// Set from the settings menu and remembered between sessions, each from 0 to 1.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self {
+        AudioVolumes {
+            master: 1.,
+            music: 0.5,
+            sfx: 1.,
+        }
+    }
+}
+
+impl AudioVolumes {
+    // `volume.json` inside `GameSettings::data_dir`, next to the leaderboard.
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("volume.json")
+    }
+
+    pub fn load(dir: &Path) -> Self {
+        match fs::read_to_string(Self::path(dir)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Ignoring unreadable volume settings: {}", err);
+                AudioVolumes::default()
+            }),
+            Err(_) => AudioVolumes::default(),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path(dir);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn music_level(&self) -> f32 {
+        self.master * self.music
+    }
+
+    pub fn sfx_level(&self) -> f32 {
+        self.master * self.sfx
+    }
+}
+
+// Sound effects are generated tones, the music comes from `GameSettings::music`.
+#[derive(Resource, Clone)]
+pub struct AudioAssets {
+    pub click: Handle<Pitch>,
+    pub hit: Handle<Pitch>,
+    pub miss: Handle<Pitch>,
+    pub game_over: Handle<Pitch>,
+    // One per countdown second, rising in pitch as time runs out.
+    pub ticks: Vec<Handle<Pitch>>,
+    pub menu_music: Option<Handle<AudioSource>>,
+    pub game_music: Option<Handle<AudioSource>>,
+}
+
+pub fn load_audio(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+    mut pitches: ResMut<Assets<Pitch>>,
+) {
+    if settings.music.menu.is_none() && settings.music.game.is_none() {
+        info!("No music set in the settings, playing without");
+    }
+    let mut tone = |frequency: f32, millis: u64| {
+        pitches.add(Pitch::new(frequency, Duration::from_millis(millis)))
+    };
+    commands.insert_resource(AudioAssets {
+        click: tone(1200., 30),
+        hit: tone(880., 120),
+        miss: tone(180., 250),
+        game_over: tone(130., 800),
+        ticks: (0..COUNTDOWN_TICKS)
+            .map(|tick| tone(600. + 100. * tick as f32, 60))
+            .collect(),
+        menu_music: settings
+            .music
+            .menu
+            .as_ref()
+            .map(|path| asset_server.load(path)),
+        game_music: settings
+            .music
+            .game
+            .as_ref()
+            .map(|path| asset_server.load(path)),
+    });
+}
+
+#[derive(Component)]
+pub struct Music;
+
+#[derive(Component)]
+pub struct SoundEffect;
+
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioVolumes>()
+            .add_systems(Startup, load_volumes)
+            .add_systems(Update, switch_music.run_if(state_changed::<AppState>()))
+            .add_systems(
+                Update,
+                (set_music_volume, save_volumes).run_if(resource_changed::<AudioVolumes>()),
+            )
+            .add_systems(Update, countdown_ticks.run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), play_game_over_sound);
+    }
+}
+
+pub fn play_sound_effect(commands: &mut Commands, sound: &Handle<Pitch>, volume: f32) {
+    commands.spawn((
+        PitchBundle {
+            source: sound.clone(),
+            settings: PlaybackSettings {
+                volume: Volume::new_relative(volume),
+                ..PlaybackSettings::DESPAWN
+            },
+        },
+        SoundEffect,
+    ));
+}
+
+// The menu screens share one track and a game in progress another, so the music only restarts
+// when moving between the two.
+fn switch_music(
+    mut commands: Commands,
+    state: Res<State<AppState>>,
+    audio: Res<AudioAssets>,
+    volumes: Res<AudioVolumes>,
+    music_query: Query<(Entity, &Handle<AudioSource>), With<Music>>,
+) {
+    let track = match state.get() {
+        AppState::Menu | AppState::Settings | AppState::Instructions | AppState::Leaderboard => {
+            audio.menu_music.as_ref()
+        }
+        AppState::GameStart | AppState::InGame | AppState::Paused | AppState::NextRound => {
+            audio.game_music.as_ref()
+        }
+        AppState::GameOver | AppState::NameEntry => None,
+    };
+
+    let mut playing = false;
+    for (entity, current) in music_query.iter() {
+        if Some(current) == track {
+            playing = true;
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+    if let Some(track) = track.filter(|_| !playing) {
+        commands.spawn((
+            AudioBundle {
+                source: track.clone(),
+                settings: PlaybackSettings {
+                    volume: Volume::new_relative(volumes.music_level()),
+                    ..PlaybackSettings::LOOP
+                },
+            },
+            Music,
+        ));
+    }
+}
+
+fn set_music_volume(volumes: Res<AudioVolumes>, sink_query: Query<&AudioSink, With<Music>>) {
+    for sink in sink_query.iter() {
+        sink.set_volume(volumes.music_level());
+    }
+}
+
+// Waits for the settings, which say where the volumes are kept.
+fn load_volumes(mut commands: Commands, settings: Res<GameSettings>) {
+    commands.insert_resource(AudioVolumes::load(&settings.data_dir()));
+}
+
+fn save_volumes(volumes: Res<AudioVolumes>, settings: Res<GameSettings>) {
+    // Nothing has changed yet when they've only just been loaded.
+    if volumes.is_added() {
+        return;
+    }
+    if let Err(err) = volumes.save(&settings.data_dir()) {
+        error!("Failed to save volume settings: {}", err);
+    }
+}
+
+// Ticks once a second over the final seconds of a game, higher and louder as time runs out.
+fn countdown_ticks(
+    mut commands: Commands,
+    mut last_tick: Local<Option<usize>>,
+    game_state: Res<GameState>,
+    audio: Res<AudioAssets>,
+    volumes: Res<AudioVolumes>,
+) {
+    let remaining = game_state.remaining_time().as_secs_f32().ceil() as usize;
+    if remaining == 0 || remaining > COUNTDOWN_TICKS {
+        *last_tick = None;
+        return;
+    }
+    if *last_tick == Some(remaining) {
+        return;
+    }
+    *last_tick = Some(remaining);
+
+    let tick = COUNTDOWN_TICKS - remaining;
+    let loudness = 0.5 + 0.5 * tick as f32 / (COUNTDOWN_TICKS - 1) as f32;
+    play_sound_effect(
+        &mut commands,
+        &audio.ticks[tick],
+        volumes.sfx_level() * loudness,
+    );
+}
+
+fn play_game_over_sound(
+    mut commands: Commands,
+    audio: Res<AudioAssets>,
+    volumes: Res<AudioVolumes>,
+) {
+    play_sound_effect(&mut commands, &audio.game_over, volumes.sfx_level());
+}
@@ -0,0 +1,178 @@
+use bevy::asset::LoadState;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+// A way of speaking the color words, standing for the palette color in the same position.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Voice {
+    pub name: String,
+    // Folder inside the assets folder holding a recording of each palette color's name, e.g.
+    // `voices/female/RED.ogg`.
+    pub folder: String,
+    // Playback speed, which shifts the pitch too, so one set of recordings can make several
+    // voices.
+    pub speed: f32,
+}
+
+// One set of recordings played at five different pitches, for the five default colors.
+pub fn default_voices() -> Vec<Voice> {
+    [
+        ("lowest", 0.7),
+        ("low", 0.85),
+        ("middle", 1.),
+        ("high", 1.2),
+        ("highest", 1.4),
+    ]
+    .iter()
+    .map(|(name, speed)| Voice {
+        name: name.to_string(),
+        folder: "voices".to_string(),
+        speed: *speed,
+    })
+    .collect()
+}
+
+// Each voice's recordings, in palette order. Only loaded for auditory sessions.
+#[derive(Resource, Clone)]
+pub struct VoiceClips(pub Vec<Vec<Handle<AudioSource>>>);
+
+pub fn load_voice_clips(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if settings.stimulus != Stimulus::Auditory {
+        return;
+    }
+    commands.insert_resource(VoiceClips(
+        settings
+            .voices
+            .iter()
+            .map(|voice| {
+                settings
+                    .palette
+                    .iter()
+                    .map(|color| asset_server.load(format!("{}/{}.ogg", voice.folder, color.name)))
+                    .collect()
+            })
+            .collect(),
+    ));
+}
+
+// A recording that failed to load, most likely because it isn't in the assets folder. Auditory
+// sessions won't start without every recording, rather than play rounds with nothing to hear.
+#[derive(Resource, Clone, Debug)]
+pub struct MissingVoiceClip(pub String);
+
+// The voices don't ship with the game, so a missing recording is a setup mistake to report.
+// Once every clip has loaded there's nothing left to check.
+fn check_voice_clips(
+    mut commands: Commands,
+    clips: Res<VoiceClips>,
+    asset_server: Res<AssetServer>,
+    mut all_loaded: Local<bool>,
+) {
+    if *all_loaded {
+        return;
+    }
+    let mut clips = clips.0.iter().flatten();
+    if clips
+        .clone()
+        .all(|clip| asset_server.get_load_state(clip.id()) == Some(LoadState::Loaded))
+    {
+        *all_loaded = true;
+        return;
+    }
+    let Some(path) = clips
+        .find(|clip| asset_server.get_load_state(clip.id()) == Some(LoadState::Failed))
+        .map(|clip| {
+            clip.path()
+                .map_or_else(String::new, |path| path.to_string())
+        })
+    else {
+        return;
+    };
+    error!("Couldn't load the voice recording {}", path);
+    commands.insert_resource(MissingVoiceClip(path));
+}
+
+// Stays on screen for the rest of the session, the menus refuse to start a game.
+fn show_missing_voice_clip(
+    mut commands: Commands,
+    missing: Res<MissingVoiceClip>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    spawn_error(
+        &mut commands,
+        &fonts,
+        locale.format("error.missing_voice", &[("path", &missing.0)]),
+    );
+}
+
+// The color word of an auditory trial, played once when the round starts and cut off if the
+// round ends first.
+#[derive(Component, Clone, Debug)]
+pub struct SpokenWord {
+    pub clip: Handle<AudioSource>,
+    pub speed: f32,
+}
+
+// The recording of the palette color `word` in the voice standing for the palette color `ink`.
+pub fn spoken_word(
+    settings: &GameSettings,
+    voice_clips: Option<&VoiceClips>,
+    word: &str,
+    ink: &str,
+) -> Option<SpokenWord> {
+    let index = |name: &str| settings.palette.iter().position(|color| color.name == name);
+    let spoken = index(ink).and_then(|voice| {
+        Some(SpokenWord {
+            clip: voice_clips?.0.get(voice)?.get(index(word)?)?.clone(),
+            speed: settings.voices.get(voice)?.speed,
+        })
+    });
+    if spoken.is_none() {
+        warn!("No recording of {} for the {} voice", word, ink);
+    }
+    spoken
+}
+
+pub struct AuditoryPlugin;
+
+impl Plugin for AuditoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                speak_words,
+                check_voice_clips.run_if(
+                    resource_exists::<VoiceClips>()
+                        .and_then(not(resource_exists::<MissingVoiceClip>())),
+                ),
+                show_missing_voice_clip.run_if(resource_added::<MissingVoiceClip>()),
+            ),
+        );
+    }
+}
+
+// The word is the stimulus rather than an effect, so only the master volume applies.
+fn speak_words(
+    mut commands: Commands,
+    volumes: Res<AudioVolumes>,
+    word_query: Query<(Entity, &SpokenWord), Added<SpokenWord>>,
+) {
+    for (entity, word) in word_query.iter() {
+        commands.entity(entity).insert(AudioBundle {
+            source: word.clip.clone(),
+            settings: PlaybackSettings {
+                speed: word.speed,
+                volume: Volume::new_relative(volumes.master),
+                ..PlaybackSettings::ONCE
+            },
+        });
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+// Pairs closer than this (CIE76 delta E) are hard to tell apart at a glance, the simulation
+// warns about them.
+pub const MIN_COLOR_DIFFERENCE: f32 = 25.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ColorVision {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorVision {
+    // Built-in palette that stays distinguishable with this deficiency as well as with normal
+    // color vision. Protanopia and deuteranopia confuse the same colors, so they share one.
+    pub fn palette(&self) -> Vec<PaletteColor> {
+        let colors: [(&str, (f32, f32, f32)); 5] = match self {
+            ColorVision::Protanopia | ColorVision::Deuteranopia => [
+                ("BLUE", (0.1, 0.3, 1.)),
+                ("ORANGE", (0.85, 0.45, 0.)),
+                ("YELLOW", (1., 1., 0.2)),
+                ("WHITE", (1., 1., 1.)),
+                ("GREY", (0.45, 0.45, 0.45)),
+            ],
+            ColorVision::Tritanopia => [
+                ("RED", (0.9, 0.1, 0.1)),
+                ("GREEN", (0., 0.7, 0.3)),
+                ("PINK", (1., 0.6, 0.8)),
+                ("WHITE", (1., 1., 1.)),
+                ("GREY", (0.45, 0.45, 0.45)),
+            ],
+        };
+        colors
+            .iter()
+            .map(|(name, rgb)| PaletteColor {
+                name: name.to_string(),
+                rgb: *rgb,
+                key: None,
+            })
+            .collect()
+    }
+
+    // Linear RGB transforms from Machado, Oliveira & Fernandes (2009) at full severity.
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVision::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    // How `color` looks to someone with this deficiency.
+    pub fn simulate(&self, color: Color) -> Color {
+        let [r, g, b, a] = color.as_linear_rgba_f32();
+        let [r, g, b] = self
+            .matrix()
+            .map(|row| (row[0] * r + row[1] * g + row[2] * b).clamp(0., 1.));
+        Color::rgba_linear(r, g, b, a)
+    }
+
+    // None, then each deficiency in turn, for cycling the simulation with a key.
+    pub fn cycle(current: Option<ColorVision>) -> Option<ColorVision> {
+        match current {
+            None => Some(ColorVision::Protanopia),
+            Some(ColorVision::Protanopia) => Some(ColorVision::Deuteranopia),
+            Some(ColorVision::Deuteranopia) => Some(ColorVision::Tritanopia),
+            Some(ColorVision::Tritanopia) => None,
+        }
+    }
+}
+
+fn lab(color: Color) -> [f32; 3] {
+    let [r, g, b, _] = color.as_linear_rgba_f32();
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f32| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16. / 116.
+        }
+    };
+    [
+        116. * f(y) - 16.,
+        500. * (f(x) - f(y)),
+        200. * (f(y) - f(z)),
+    ]
+}
+
+// CIE76 delta E, roughly 2.3 is the smallest difference anyone can see.
+pub fn color_difference(a: Color, b: Color) -> f32 {
+    let (a, b) = (lab(a), lab(b));
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+// The two least distinguishable colors and their difference, as seen with `vision`.
+pub fn closest_pair(colors: &[Color], vision: Option<ColorVision>) -> Option<(usize, usize, f32)> {
+    let seen: Vec<Color> = colors
+        .iter()
+        .map(|color| vision.map_or(*color, |vision| vision.simulate(*color)))
+        .collect();
+    (0..seen.len())
+        .flat_map(|first| (first + 1..seen.len()).map(move |second| (first, second)))
+        .map(|(first, second)| (first, second, color_difference(seen[first], seen[second])))
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+}
+
+// The deficiency everything on screen is currently drawn as, if any.
+#[derive(Resource, Default)]
+pub struct CvdSimulation(pub Option<ColorVision>);
+
+pub struct ColorVisionPlugin;
+
+impl Plugin for ColorVisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CvdSimulation>()
+            .add_systems(Startup, start_cvd_simulation)
+            .add_systems(
+                Update,
+                (
+                    toggle_cvd_simulation,
+                    apply_cvd_simulation.run_if(resource_changed::<CvdSimulation>()),
+                )
+                    .chain(),
+            )
+            .add_systems(PostUpdate, (simulate_text_colors, simulate_ui_colors));
+    }
+}
+
+fn start_cvd_simulation(mut simulation: ResMut<CvdSimulation>, settings: Res<GameSettings>) {
+    simulation.0 = settings.cvd_simulation;
+}
+
+// F9 steps through the simulations, a design aid rather than a player setting.
+fn toggle_cvd_simulation(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut simulation: ResMut<CvdSimulation>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F9) {
+        simulation.0 = ColorVision::cycle(simulation.0);
+    }
+}
+
+// Redraws the target circles as they'd be seen with the simulated deficiency. `ColorResource`
+// keeps the real colors, text and UI colors are redrawn by `simulate_text_colors` and
+// `simulate_ui_colors`.
+fn apply_cvd_simulation(
+    simulation: Res<CvdSimulation>,
+    settings: Res<GameSettings>,
+    colors: Res<ColorResource>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let originals: Vec<Color> = settings.palette.iter().map(PaletteColor::color).collect();
+    for ((material, _, _), original) in colors.iter().zip(&originals) {
+        if let Some(material) = materials.get_mut(material.id()) {
+            material.color = simulation
+                .0
+                .map_or(*original, |vision| vision.simulate(*original));
+        }
+    }
+
+    if let Some((first, second, difference)) = closest_pair(&originals, simulation.0) {
+        let (first, second) = (
+            &settings.palette[first].name,
+            &settings.palette[second].name,
+        );
+        match simulation.0 {
+            Some(vision) if difference < MIN_COLOR_DIFFERENCE => warn!(
+                "With {:?}, {} and {} are hard to tell apart (delta E {:.1})",
+                vision, first, second, difference
+            ),
+            Some(vision) => info!(
+                "With {:?}, the closest colors are {} and {} (delta E {:.1})",
+                vision, first, second, difference
+            ),
+            None => info!("Color vision simulation off"),
+        }
+    }
+}
+
+// The color something was given and the color it's drawn as. If it's still drawn as `shown`,
+// apart from a faded alpha, nothing else has set it since and `given` is still the real color.
+fn simulated(
+    previous: Option<(Color, Color)>,
+    current: Color,
+    vision: Option<ColorVision>,
+) -> (Color, Color) {
+    let given = match previous {
+        Some((given, shown)) if same_rgb(current, shown) => given.with_a(current.a()),
+        _ => current,
+    };
+    (given, vision.map_or(given, |vision| vision.simulate(given)))
+}
+
+fn same_rgb(a: Color, b: Color) -> bool {
+    let (a, b) = (a.as_linear_rgba_f32(), b.as_linear_rgba_f32());
+    a.iter().zip(&b).take(3).all(|(a, b)| (a - b).abs() < 1e-4)
+}
+
+// Draws every text section with the simulated deficiency as soon as it's switched on, including
+// the stimulus word and the feedback, and whenever a text's colors are set afterwards.
+fn simulate_text_colors(
+    simulation: Res<CvdSimulation>,
+    mut text_query: Query<(Entity, &mut Text)>,
+    mut colors: Local<HashMap<Entity, Vec<(Color, Color)>>>,
+) {
+    colors.retain(|entity, _| text_query.contains(*entity));
+    for (entity, mut text) in text_query.iter_mut() {
+        let tracked = colors.get(&entity);
+        if !simulation.is_changed() && !text.is_changed() && tracked.is_some() {
+            continue;
+        }
+        let updated: Vec<(Color, Color)> = text
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                let previous = tracked.and_then(|tracked| tracked.get(index)).copied();
+                simulated(previous, section.style.color, simulation.0)
+            })
+            .collect();
+        for (section, (_, shown)) in text.sections.iter_mut().zip(&updated) {
+            if section.style.color != *shown {
+                section.style.color = *shown;
+            }
+        }
+        colors.insert(entity, updated);
+    }
+}
+
+// The same for UI backgrounds, e.g. the menu buttons.
+fn simulate_ui_colors(
+    simulation: Res<CvdSimulation>,
+    mut background_query: Query<(Entity, &mut BackgroundColor)>,
+    mut colors: Local<HashMap<Entity, (Color, Color)>>,
+) {
+    colors.retain(|entity, _| background_query.contains(*entity));
+    for (entity, mut background) in background_query.iter_mut() {
+        let tracked = colors.get(&entity).copied();
+        if !simulation.is_changed() && !background.is_changed() && tracked.is_some() {
+            continue;
+        }
+        let (given, shown) = simulated(tracked, background.0, simulation.0);
+        if background.0 != shown {
+            background.0 = shown;
+        }
+        colors.insert(entity, (given, shown));
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+// Counts are answered with the number keys, so only the first nine palette colors are used.
+pub const MAX_COUNT: usize = 9;
+// Recorded as each counting trial's rule.
+pub const COUNTING_RULE: &str = "How Many";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CountingSettings {
+    // Share of trials that repeat `glyph` instead of a digit, a baseline with no number to
+    // ignore.
+    pub neutral_ratio: f64,
+    pub glyph: String,
+}
+
+impl Default for CountingSettings {
+    fn default() -> Self {
+        CountingSettings {
+            neutral_ratio: 0.2,
+            glyph: "#".to_string(),
+        }
+    }
+}
+
+impl CountingSettings {
+    // Returns why these can't be used, if they can't.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0. ..=1.).contains(&self.neutral_ratio) {
+            return Err(format!(
+                "counting.neutral_ratio needs to be between 0 and 1, not {}",
+                self.neutral_ratio
+            ));
+        }
+        Ok(())
+    }
+}
+
+// The counting Stroop stimulus, `item` repeated `count` times, e.g. "3 3" for a count of two.
+pub fn counting_stimulus(item: &str, count: usize) -> String {
+    vec![item; count].join(" ")
+}
+
+// The count a palette color stands for, the first color is one and so on.
+pub fn color_count(colors: &ColorResource, material: &Handle<ColorMaterial>) -> Option<usize> {
+    colors
+        .iter()
+        .position(|(color, _, _)| color == material)
+        .map(|index| index + 1)
+}
+
+// The incorrect circle's number repeated as many times as the correct circle's number, or the
+// correct circle's own number when congruent, or `glyph` on a neutral trial.
+pub fn counting_trial(
+    settings: &CountingSettings,
+    congruent_ratio: f64,
+    colors: &ColorResource,
+    (correct, wrong): (&Target, &Target),
+    rng: &mut impl Rng,
+) -> StimulusTrial {
+    let congruency = if rng.gen_bool(settings.neutral_ratio.clamp(0., 1.)) {
+        Congruency::Neutral
+    } else if rng.gen_bool(congruent_ratio.clamp(0., 1.)) {
+        Congruency::Congruent
+    } else {
+        Congruency::Incongruent
+    };
+    let count = |target: &Target| color_count(colors, &target.0).unwrap_or_default();
+    let item = match congruency {
+        Congruency::Congruent => count(correct).to_string(),
+        Congruency::Incongruent => count(wrong).to_string(),
+        Congruency::Neutral => settings.glyph.clone(),
+    };
+    let answer = count(correct);
+    let word = counting_stimulus(&item, answer);
+    StimulusTrial {
+        text: word.clone(),
+        word,
+        ink: answer.to_string(),
+        color: Color::WHITE,
+        rule_key: "rule.count",
+        rule: COUNTING_RULE,
+        congruency,
+        category: None,
+        spoken: None,
+    }
+}
+
+// The number on a target circle in a counting session.
+#[derive(Component)]
+pub struct CountLabel;
+
+pub struct CountingPlugin;
+
+impl Plugin for CountingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, label_target_circles);
+    }
+}
+
+// Each target circle shows the count its color stands for, so the player can click it.
+fn label_target_circles(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    colors: Res<ColorResource>,
+    fonts: Res<Fonts>,
+    circle_query: Query<(Entity, &Handle<ColorMaterial>), Added<Interactable>>,
+) {
+    if settings.stimulus != Stimulus::Counting {
+        return;
+    }
+    for (circle, material) in circle_query.iter() {
+        let Some(count) = color_count(&colors, material) else {
+            continue;
+        };
+        commands.entity(circle).with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        count.to_string(),
+                        TextStyle {
+                            font: fonts.ui.clone(),
+                            font_size: settings.circle_radius * 1.5,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    ..default()
+                },
+                CountLabel,
+            ));
+        });
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DifficultyCurve {
+    // Every round plays like the first one.
+    Flat,
+    // Gains `per_point` levels for every point scored.
+    Linear { per_point: f32 },
+    // Gains `levels` all at once every `points` points.
+    Stepped { points: usize, levels: f32 },
+    // Goes up a level after a correct answer faster than `target_reaction_ms`, down a level after
+    // a slower one, a wrong answer or a miss.
+    Adaptive { target_reaction_ms: f64 },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultySettings {
+    pub standard: DifficultyCurve,
+    pub research: DifficultyCurve,
+    // Training sets the target speed from its staircase, this only drives the other knobs. It
+    // can't be Adaptive, two loops chasing the player's answers would muddy the threshold.
+    pub training: DifficultyCurve,
+    // Fraction of `GameSettings::target_speed` added per level, up to `max_speed_multiplier`.
+    pub speed_step: f32,
+    pub max_speed_multiplier: f32,
+    // How quickly the correct and incorrect bands close in on the middle of the screen, never
+    // narrower than `min_separation` of their original height.
+    pub separation_step: f32,
+    pub min_separation: f32,
+    // Extra incorrect circles per level, up to `max_distractors`.
+    pub distractor_step: f32,
+    pub max_distractors: usize,
+}
+
+impl Default for DifficultySettings {
+    fn default() -> Self {
+        DifficultySettings {
+            standard: DifficultyCurve::Linear { per_point: 1. },
+            // Research sessions keep conditions constant so reaction times stay comparable.
+            research: DifficultyCurve::Flat,
+            training: DifficultyCurve::Flat,
+            speed_step: 0.05,
+            max_speed_multiplier: 3.,
+            separation_step: 0.1,
+            min_separation: 0.2,
+            distractor_step: 0.2,
+            max_distractors: 3,
+        }
+    }
+}
+
+impl DifficultyCurve {
+    // Returns why the curve can't be used, if it can't.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            DifficultyCurve::Linear { per_point }
+                if !(per_point.is_finite() && per_point >= 0.) =>
+            {
+                Err(format!(
+                    "per_point needs to be 0 or above, not {}",
+                    per_point
+                ))
+            }
+            DifficultyCurve::Stepped { levels, .. } if !(levels.is_finite() && levels >= 0.) => {
+                Err(format!("levels needs to be 0 or above, not {}", levels))
+            }
+            DifficultyCurve::Adaptive { target_reaction_ms }
+                if !(target_reaction_ms.is_finite() && target_reaction_ms > 0.) =>
+            {
+                Err(format!(
+                    "target_reaction_ms needs to be above 0, not {}",
+                    target_reaction_ms
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl DifficultySettings {
+    // Returns why these can't be used, if they can't. The bands, for one, can't close in to
+    // nothing or open up past the screen.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, curve) in [
+            ("standard", self.standard),
+            ("research", self.research),
+            ("training", self.training),
+        ] {
+            curve
+                .validate()
+                .map_err(|err| format!("difficulty.{}: {}", name, err))?;
+        }
+        for (name, value) in [
+            ("speed_step", self.speed_step),
+            ("separation_step", self.separation_step),
+            ("distractor_step", self.distractor_step),
+        ] {
+            if !(value.is_finite() && value >= 0.) {
+                return Err(format!(
+                    "difficulty.{} needs to be 0 or above, not {}",
+                    name, value
+                ));
+            }
+        }
+        if !(self.max_speed_multiplier.is_finite() && self.max_speed_multiplier >= 1.) {
+            return Err(format!(
+                "difficulty.max_speed_multiplier needs to be 1 or above, not {}",
+                self.max_speed_multiplier
+            ));
+        }
+        if !(self.min_separation > 0. && self.min_separation <= 1.) {
+            return Err(format!(
+                "difficulty.min_separation needs to be above 0 and at most 1, not {}",
+                self.min_separation
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn curve(&self, game_mode: &GameMode) -> DifficultyCurve {
+        match game_mode {
+            GameMode::Standard => self.standard,
+            GameMode::Research { .. } => self.research,
+            GameMode::Training => self.training,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Difficulty {
+    pub level: f32,
+    pub target_speed: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty {
+            level: 0.,
+            target_speed: GameSettings::default().target_speed,
+        }
+    }
+}
+
+impl Difficulty {
+    // 1 at level 0, shrinking towards `min_separation`.
+    pub fn separation(&self, settings: &GameSettings) -> f32 {
+        (1. / (1. + settings.difficulty.separation_step * self.level))
+            .max(settings.difficulty.min_separation)
+    }
+
+    pub fn distractors(&self, settings: &GameSettings) -> usize {
+        ((settings.difficulty.distractor_step * self.level) as usize)
+            .min(settings.difficulty.max_distractors)
+    }
+}
+
+pub fn update_difficulty(
+    mut difficulty: ResMut<Difficulty>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    settings: Res<GameSettings>,
+    trial_log: Res<TrialLog>,
+    staircase: Res<Staircase>,
+) {
+    difficulty.level = match settings.difficulty.curve(&game_mode) {
+        DifficultyCurve::Flat => 0.,
+        DifficultyCurve::Linear { per_point } => game_state.score() as f32 * per_point,
+        DifficultyCurve::Stepped { points, levels } => {
+            (game_state.score() / points.max(1)) as f32 * levels
+        }
+        DifficultyCurve::Adaptive { target_reaction_ms } => {
+            match trial_log
+                .trials
+                .last()
+                .map(|trial| (trial.outcome, trial.reaction_time_ms))
+            {
+                Some((TrialOutcome::Correct, Some(reaction_time)))
+                    if reaction_time < target_reaction_ms =>
+                {
+                    difficulty.level + 1.
+                }
+                // A fast wrong answer is still wrong.
+                Some((TrialOutcome::Correct | TrialOutcome::Wrong | TrialOutcome::Missed, _)) => {
+                    (difficulty.level - 1.).max(0.)
+                }
+                Some((TrialOutcome::Unanswered, _)) | None => difficulty.level,
+            }
+        }
+    };
+
+    difficulty.target_speed = if *game_mode == GameMode::Training {
+        staircase.speed
+    } else {
+        let multiplier = (1. + settings.difficulty.speed_step * difficulty.level)
+            .min(settings.difficulty.max_speed_multiplier);
+        settings.target_speed * multiplier
+    };
+}
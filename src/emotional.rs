@@ -0,0 +1,142 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+// Words of one kind, e.g. threat related or neutral, shown as they're written in the list.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WordCategory {
+    pub name: String,
+    pub words: Vec<String>,
+}
+
+// The word lists of the emotional Stroop task, each category is picked equally often.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmotionalWords {
+    // The category the others are compared against.
+    pub neutral: String,
+    pub categories: Vec<WordCategory>,
+}
+
+impl Default for EmotionalWords {
+    fn default() -> Self {
+        let category = |name: &str, words: &[&str]| WordCategory {
+            name: name.to_string(),
+            words: words.iter().map(|word| word.to_string()).collect(),
+        };
+        EmotionalWords {
+            neutral: "neutral".to_string(),
+            categories: vec![
+                category(
+                    "neutral",
+                    &[
+                        "TABLE", "CHAIR", "WINDOW", "PAPER", "BOTTLE", "PENCIL", "CARPET", "KETTLE",
+                    ],
+                ),
+                category(
+                    "negative",
+                    &[
+                        "DEATH", "FEAR", "GRIEF", "PAIN", "TERROR", "AGONY", "DISEASE", "FAILURE",
+                    ],
+                ),
+                category(
+                    "positive",
+                    &[
+                        "HAPPY", "LOVE", "JOY", "PEACE", "SMILE", "HOPE", "KIND", "PROUD",
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+impl EmotionalWords {
+    // Returns why the lists can't be used, if they can't.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(category) = self
+            .categories
+            .iter()
+            .find(|category| category.words.is_empty())
+        {
+            return Err(format!("The {} word list is empty", category.name));
+        }
+        if !self
+            .categories
+            .iter()
+            .any(|category| category.name == self.neutral)
+        {
+            return Err(format!("There's no {} word list", self.neutral));
+        }
+        Ok(())
+    }
+
+    // A random category, then a random word from it.
+    pub fn choose(&self, rng: &mut impl Rng) -> (String, String) {
+        let category = self.categories.choose(rng).unwrap();
+        let word = category.words.choose(rng).unwrap();
+        (category.name.clone(), word.clone())
+    }
+}
+
+// A word from a random category, printed as it's written in the correct circle's color. The
+// words don't spell a color, so the ink is always the answer.
+pub fn emotional_trial(
+    words: &EmotionalWords,
+    correct: &Target,
+    rng: &mut impl Rng,
+) -> StimulusTrial {
+    let (category, word) = words.choose(rng);
+    StimulusTrial {
+        text: word.clone(),
+        word,
+        ink: correct.1.clone(),
+        color: correct.2,
+        rule_key: WordOrColor::Color.key(),
+        rule: WordOrColor::Color.label(),
+        congruency: Congruency::Neutral,
+        category: Some(category),
+        spoken: None,
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CategorySummary {
+    pub category: String,
+    pub trials: usize,
+    pub correct: usize,
+    pub mean_rt_ms: Option<f64>,
+    // Mean reaction time minus the neutral category's, how much longer these words hold the
+    // player's attention. None for the neutral category itself.
+    pub bias_ms: Option<f64>,
+}
+
+impl CategorySummary {
+    // One per word list, in the order they're listed.
+    pub fn all(log: &TrialLog, words: &EmotionalWords) -> Vec<Self> {
+        let neutral_rt = log.mean_category_reaction_time_ms(&words.neutral);
+        words
+            .categories
+            .iter()
+            .map(|category| {
+                let trials = log
+                    .trials
+                    .iter()
+                    .filter(|trial| trial.category.as_deref() == Some(category.name.as_str()));
+                let mean_rt_ms = log.mean_category_reaction_time_ms(&category.name);
+                CategorySummary {
+                    category: category.name.clone(),
+                    trials: trials.clone().count(),
+                    correct: trials
+                        .filter(|trial| trial.outcome == TrialOutcome::Correct)
+                        .count(),
+                    mean_rt_ms,
+                    bias_ms: mean_rt_ms
+                        .zip(neutral_rt)
+                        .filter(|_| category.name != words.neutral)
+                        .map(|(rt, neutral)| rt - neutral),
+                }
+            })
+            .collect()
+    }
+}
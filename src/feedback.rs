@@ -0,0 +1,223 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+const PARTICLES: usize = 12;
+const PARTICLE_SPEED: f32 = 250.;
+const PARTICLE_SCALE: f32 = 0.3;
+const FLASHES: f32 = 3.;
+
+// Each effect can be turned off on its own, some research protocols want no feedback at all.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedbackSettings {
+    // A burst of particles where the correct circle was answered.
+    pub hit_burst: bool,
+    // The wrong circle that was picked blinks in place.
+    pub wrong_flash: bool,
+    // "+1" rising from the correct circle.
+    pub score_popup: bool,
+    // A click for every response, then a hit or a miss, using the tones from `AudioAssets`.
+    pub sounds: bool,
+}
+
+impl Default for FeedbackSettings {
+    fn default() -> Self {
+        FeedbackSettings {
+            hit_burst: true,
+            wrong_flash: true,
+            score_popup: true,
+            sounds: true,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HitParticle {
+    velocity: Vec2,
+    lifetime: Timer,
+}
+
+#[derive(Component)]
+pub struct WrongFlash(Timer);
+
+#[derive(Component)]
+pub struct ScorePopup(Timer);
+
+pub struct FeedbackPlugin;
+
+impl Plugin for FeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_feedback_effects,
+                move_hit_particles,
+                blink_wrong_flashes,
+                raise_score_popups,
+            ),
+        )
+        .add_systems(
+            Update,
+            play_answer_sounds.run_if(resource_exists::<AudioAssets>()),
+        );
+    }
+}
+
+// The effects belong to the round they were answered in, so they're gone by game over and
+// freeze along with everything else while paused.
+fn spawn_feedback_effects(
+    mut commands: Commands,
+    mut resolved_event: EventReader<TrialResolvedEvent>,
+    settings: Res<GameSettings>,
+    mesh: Res<MeshResource>,
+    fonts: Res<Fonts>,
+) {
+    for event in resolved_event.read() {
+        let Some(position) = event.position else {
+            continue;
+        };
+
+        if event.outcome == TrialOutcome::Correct && settings.feedback.hit_burst {
+            for particle in 0..PARTICLES {
+                let direction = Vec2::from_angle(TAU * particle as f32 / PARTICLES as f32);
+                commands.spawn((
+                    MaterialMesh2dBundle {
+                        mesh: mesh.0.clone(),
+                        material: event.correct_material.clone(),
+                        transform: Transform::from_translation(position.extend(10.))
+                            .with_scale(Vec3::splat(PARTICLE_SCALE)),
+                        ..default()
+                    },
+                    HitParticle {
+                        velocity: direction * PARTICLE_SPEED,
+                        lifetime: Timer::from_seconds(0.4, TimerMode::Once),
+                    },
+                    StateScoped(ROUND_STATES),
+                ));
+            }
+        }
+
+        let wrong_circle = event
+            .wrong_circle
+            .as_ref()
+            .filter(|_| settings.feedback.wrong_flash);
+        if let Some((center, material)) = wrong_circle {
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    mesh: mesh.0.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_translation(center.extend(10.))
+                        .with_scale(Vec3::splat(1.5)),
+                    ..default()
+                },
+                WrongFlash(Timer::from_seconds(0.5, TimerMode::Once)),
+                StateScoped(ROUND_STATES),
+            ));
+        }
+
+        if event.points > 0 && settings.feedback.score_popup {
+            commands.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        format!("+{}", event.points),
+                        TextStyle {
+                            font: fonts.ui.clone(),
+                            font_size: 50.0,
+                            color: Color::GREEN,
+                        },
+                    ),
+                    transform: Transform::from_translation(position.extend(20.)),
+                    ..default()
+                },
+                ScorePopup(Timer::from_seconds(0.8, TimerMode::Once)),
+                StateScoped(ROUND_STATES),
+            ));
+        }
+    }
+}
+
+// Only runs once `load_audio` has made the tones.
+fn play_answer_sounds(
+    mut commands: Commands,
+    mut resolved_event: EventReader<TrialResolvedEvent>,
+    mut click_event: EventReader<LeftClickEvent>,
+    mut color_response_event: EventReader<ColorResponseEvent>,
+    settings: Res<GameSettings>,
+    audio: Res<AudioAssets>,
+    volumes: Res<AudioVolumes>,
+) {
+    let responses = click_event.read().count() + color_response_event.read().count();
+    let resolved: Vec<TrialOutcome> = resolved_event.read().map(|event| event.outcome).collect();
+    if !settings.feedback.sounds {
+        return;
+    }
+    for _ in 0..responses {
+        play_sound_effect(&mut commands, &audio.click, volumes.sfx_level());
+    }
+    for outcome in resolved {
+        let sound = match outcome {
+            TrialOutcome::Correct => &audio.hit,
+            _ => &audio.miss,
+        };
+        play_sound_effect(&mut commands, sound, volumes.sfx_level());
+    }
+}
+
+// Flies outward and shrinks away.
+fn move_hit_particles(
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut HitParticle, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut particle, mut transform) in particle_query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.);
+        transform.scale = Vec3::splat(PARTICLE_SCALE * particle.lifetime.percent_left());
+    }
+}
+
+fn blink_wrong_flashes(
+    mut commands: Commands,
+    mut flash_query: Query<(Entity, &mut WrongFlash, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut visibility) in flash_query.iter_mut() {
+        flash.0.tick(time.delta());
+        if flash.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let blink = (flash.0.percent() * FLASHES * 2.) as u32;
+        *visibility = if blink.is_multiple_of(2) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// Drifts upward while fading out.
+fn raise_score_popups(
+    mut commands: Commands,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut popup, mut transform, mut text) in popup_query.iter_mut() {
+        popup.0.tick(time.delta());
+        if popup.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += 60. * time.delta_seconds();
+        text.sections[0].style.color.set_a(popup.0.percent_left());
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+pub const STIMULUS_FONT_SIZE: f32 = 100.;
+
+// Font files are paths inside the assets folder, e.g. "fonts/FiraSans-Bold.ttf". Bevy has no
+// font weight setting, so a weight is picked by pointing at that weight's file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    // Menus, HUD and messages, Bevy's built-in font when left out.
+    pub ui: Option<String>,
+    // The color word the player responds to, the UI font when left out. Font choice changes
+    // how quickly the word is read, so it's kept separate from everything else.
+    pub stimulus: Option<String>,
+    pub stimulus_size: f32,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        FontSettings {
+            ui: None,
+            stimulus: None,
+            stimulus_size: STIMULUS_FONT_SIZE,
+        }
+    }
+}
+
+// The fonts every `TextStyle` is built from. The default handles are Bevy's built-in font.
+#[derive(Resource, Clone, Debug)]
+pub struct Fonts {
+    pub ui: Handle<Font>,
+    pub stimulus: Handle<Font>,
+    pub stimulus_size: f32,
+}
+
+impl Default for Fonts {
+    fn default() -> Self {
+        Fonts {
+            ui: Handle::default(),
+            stimulus: Handle::default(),
+            stimulus_size: STIMULUS_FONT_SIZE,
+        }
+    }
+}
+
+pub fn load_fonts(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    let ui = settings
+        .fonts
+        .ui
+        .as_ref()
+        .map_or_else(Handle::default, |path| asset_server.load(path));
+    let stimulus = settings
+        .fonts
+        .stimulus
+        .as_ref()
+        .map_or_else(|| ui.clone(), |path| asset_server.load(path));
+    commands.insert_resource(Fonts {
+        ui,
+        stimulus,
+        stimulus_size: settings.fonts.stimulus_size,
+    });
+}
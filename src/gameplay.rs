@@ -1,26 +1,155 @@
 use crate::*;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use rand::prelude::*;
+use std::time::Duration;
 
 #[derive(Resource)]
 pub struct GameState {
     score: usize,
     remaining_time: Timer,
     timer_expired: bool,
+    lives: usize,
 }
 
-#[derive(Clone)]
-pub enum CorrectIncorrect {
-    Correct,
-    Incorrect,
+impl GameState {
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn timer_expired(&self) -> bool {
+        self.timer_expired
+    }
+
+    pub fn lives(&self) -> usize {
+        self.lives
+    }
+
+    pub fn remaining_time(&self) -> Duration {
+        self.remaining_time.remaining()
+    }
+}
+
+// Present while a practice block is running. Practice rounds aren't timed, never cost a life or
+// score a point, and the real game starts once `trials` of them have been answered.
+#[derive(Resource, Clone, Debug)]
+pub struct Practice {
+    pub trials: usize,
+    pub completed: usize,
+}
+
+impl Practice {
+    pub fn new(trials: usize) -> Self {
+        Practice {
+            trials: trials.max(1),
+            completed: 0,
+        }
+    }
+}
+
+pub const DEFAULT_CONGRUENT_RATIO: f64 = 0.5;
+// Enough congruent trials that neither rule can be followed without reading it.
+pub const STANDARD_CONGRUENT_RATIO: f64 = 0.25;
+
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub enum GameMode {
+    Standard,
+    // Sets its own share of congruent trials (word matches ink), so the Stroop effect can be
+    // measured.
+    Research { congruent_ratio: f64 },
+    // Errors don't end the game, and a staircase adjusts target speed to the player's accuracy.
+    Training,
 }
 
-#[derive(Clone)]
+impl GameMode {
+    // `--mode research [--congruent-ratio 0.5]` or `--mode training`, anything else is the
+    // standard game.
+    pub fn from_args() -> Self {
+        match cli_arg("--mode").as_deref() {
+            Some("research") => GameMode::Research {
+                congruent_ratio: cli_arg("--congruent-ratio")
+                    .map(|arg| {
+                        // NaN fails the range check too, it would make `gen_bool` panic later.
+                        arg.parse()
+                            .ok()
+                            .filter(|ratio| (0. ..=1.).contains(ratio))
+                            .unwrap_or_else(|| {
+                                warn!(
+                                    "--congruent-ratio {} isn't between 0 and 1, ignoring it",
+                                    arg
+                                );
+                                DEFAULT_CONGRUENT_RATIO
+                            })
+                    })
+                    .unwrap_or(DEFAULT_CONGRUENT_RATIO),
+            },
+            Some("training") => GameMode::Training,
+            _ => GameMode::Standard,
+        }
+    }
+
+    // The mode after this one when cycling through them on the menu.
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Standard => GameMode::Research {
+                congruent_ratio: DEFAULT_CONGRUENT_RATIO,
+            },
+            GameMode::Research { .. } => GameMode::Training,
+            GameMode::Training => GameMode::Standard,
+        }
+    }
+
+    // Share of trials whose word and ink agree.
+    pub fn congruent_ratio(&self) -> f64 {
+        match self {
+            GameMode::Standard | GameMode::Training => STANDARD_CONGRUENT_RATIO,
+            GameMode::Research { congruent_ratio } => *congruent_ratio,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            GameMode::Standard => "Standard".to_string(),
+            GameMode::Research { .. } => "Research".to_string(),
+            GameMode::Training => "Training".to_string(),
+        }
+    }
+}
+
+// The rule shown next to the stimulus, saying whether the answer is the color the word spells
+// out or the color of its ink.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum WordOrColor {
     Word,
     Color,
 }
 
+impl WordOrColor {
+    // Recorded with each trial, so results read the same whatever language was shown.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WordOrColor::Word => "As Written",
+            WordOrColor::Color => "As Colored",
+        }
+    }
+
+    // The `Locale` key for the rule as shown to the player.
+    pub fn key(&self) -> &'static str {
+        match self {
+            WordOrColor::Word => "rule.word",
+            WordOrColor::Color => "rule.color",
+        }
+    }
+
+    // The same rule for a spoken word, where the voice takes the place of the ink.
+    pub fn spoken_key(&self) -> &'static str {
+        match self {
+            WordOrColor::Word => "rule.spoken",
+            WordOrColor::Color => "rule.voice",
+        }
+    }
+}
+
 #[derive(Component)]
 struct ColoredWord;
 
@@ -33,22 +162,52 @@ struct RemainingTime;
 #[derive(Component)]
 struct Score;
 
+#[derive(Component)]
+struct Lives;
+
+// Short-lived message shown when a life is lost, fading out as the timer runs down.
+#[derive(Component)]
+struct Feedback(Timer);
+
 #[derive(Component)]
 struct GameOverText;
 
-pub const CORRECT_OR_INCORRECT: [CorrectIncorrect; 2] =
-    [CorrectIncorrect::Correct, CorrectIncorrect::Incorrect];
+pub const WORD_OR_COLOR: [WordOrColor; 2] = [WordOrColor::Word, WordOrColor::Color];
 
 #[derive(Event)]
 pub struct MissedCircleEvent;
 
+// Sent once a trial is answered or missed, for effects that play no part in the game itself.
+#[derive(Event, Clone, Debug)]
+pub struct TrialResolvedEvent {
+    pub outcome: TrialOutcome,
+    // Where the answer landed, the click or the circle picked by key. None for a miss.
+    pub position: Option<Vec2>,
+    pub correct_material: Handle<ColorMaterial>,
+    // Center and material of the wrong circle that was picked, if one was.
+    pub wrong_circle: Option<(Vec2, Handle<ColorMaterial>)>,
+    // Added to the score by this answer.
+    pub points: usize,
+}
+
 pub struct GameplayPlugin;
 
 impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<MissedCircleEvent>()
+            .add_event::<TrialResolvedEvent>()
+            .init_resource::<TrialLog>()
             .add_systems(OnEnter(AppState::GameStart), setup_game)
-            .add_systems(OnEnter(AppState::NextRound), score_and_spawn_new_circles)
+            .init_resource::<Difficulty>()
+            .add_systems(
+                OnEnter(AppState::NextRound),
+                (
+                    update_staircase,
+                    update_difficulty,
+                    score_and_spawn_new_circles,
+                )
+                    .chain(),
+            )
             .add_systems(
                 Update,
                 move_target_circles.run_if(in_state(AppState::InGame)),
@@ -57,18 +216,38 @@ impl Plugin for GameplayPlugin {
                 Update,
                 process_events_and_timers.run_if(in_state(AppState::InGame)),
             )
+            .add_systems(Update, update_lives_text.run_if(in_state(AppState::InGame)))
+            .add_systems(Update, fade_feedback)
             .add_systems(OnEnter(AppState::GameOver), game_over)
             .add_systems(Update, game_over_input.run_if(in_state(AppState::GameOver)));
     }
 }
 
-fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<AppState>>) {
+fn setup_game(
+    mut commands: Commands<'_, '_>,
+    mut next_state: ResMut<NextState<AppState>>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+    practice: Option<ResMut<Practice>>,
+) {
+    // Restarting part way through a practice block starts the block over.
+    if let Some(mut practice) = practice {
+        practice.completed = 0;
+    }
+    commands.insert_resource(GameRng::from_seed_or_entropy(settings.seed));
+    commands.insert_resource(Difficulty::default());
+    commands.insert_resource(Staircase::new(&settings.staircase));
+
     let game_state = GameState {
         score: 0,
-        remaining_time: Timer::from_seconds(60., TimerMode::Once),
+        remaining_time: Timer::from_seconds(settings.round_seconds, TimerMode::Once),
         timer_expired: false,
+        lives: settings.lives.max(1),
     };
     commands.insert_resource(game_state);
+    commands.insert_resource(TrialLog::default());
     next_state.set(AppState::NextRound);
 
     commands.spawn((
@@ -77,10 +256,9 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             // Accepts a `String` or any type that converts into a `String`, such as `&str`
             "",
             TextStyle {
-                // This font is loaded and will be used instead of the default font.
-                font_size: 100.0,
+                font: fonts.stimulus.clone(),
+                font_size: fonts.stimulus_size,
                 color: Color::WHITE,
-                ..default()
             },
         ) // Set the justification of the Text
         .with_background_color(Color::BLACK)
@@ -94,6 +272,7 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             ..default()
         }),
         ColoredWord,
+        StateScoped(ROUND_STATES),
     ));
 
     commands.spawn((
@@ -102,10 +281,9 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             // Accepts a `String` or any type that converts into a `String`, such as `&str`
             "",
             TextStyle {
-                // This font is loaded and will be used instead of the default font.
+                font: fonts.ui.clone(),
                 font_size: 40.0,
                 color: Color::WHITE,
-                ..default()
             },
         ) // Set the justification of the Text
         .with_background_color(Color::BLACK)
@@ -117,6 +295,7 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             ..default()
         }),
         ColoredOrWord,
+        StateScoped(ROUND_STATES),
     ));
 
     commands.spawn((
@@ -125,10 +304,9 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             // Accepts a `String` or any type that converts into a `String`, such as `&str`
             "",
             TextStyle {
-                // This font is loaded and will be used instead of the default font.
+                font: fonts.ui.clone(),
                 font_size: 100.0,
                 color: Color::WHITE,
-                ..default()
             },
         ) // Set the justification of the Text
         .with_background_color(Color::BLACK)
@@ -140,37 +318,74 @@ fn setup_game(mut commands: Commands<'_, '_>, mut next_state: ResMut<NextState<A
             ..default()
         }),
         RemainingTime,
+        StateScoped(ROUND_STATES),
     ));
 
-    commands.spawn((
-        // Create a TextBundle that has a Text with a single section.
-        TextBundle::from_section(
-            // Accepts a `String` or any type that converts into a `String`, such as `&str`
-            "0",
-            TextStyle {
-                // This font is loaded and will be used instead of the default font.
-                font_size: 100.0,
-                color: Color::WHITE,
+    // The lives left sit on the same row as the score, lined up along its bottom edge.
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.0),
+                    left: Val::Percent(20.0),
+                    align_items: AlignItems::FlexEnd,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
                 ..default()
             },
-        ) // Set the justification of the Text
-        .with_background_color(Color::BLACK)
-        // Set the style of the TextBundle itself.
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            top: Val::Px(5.0),
-            left: Val::Percent(20.0),
-            ..default()
-        }),
-        Score,
-    ));
+            StateScoped(ROUND_STATES),
+        ))
+        .with_children(|row| {
+            row.spawn((
+                // Create a TextBundle that has a Text with a single section.
+                TextBundle::from_section(
+                    // Accepts a `String` or any type that converts into a `String`, such as `&str`
+                    "0",
+                    TextStyle {
+                        font: fonts.ui.clone(),
+                        font_size: 100.0,
+                        color: Color::WHITE,
+                    },
+                ) // Set the justification of the Text
+                .with_background_color(Color::BLACK),
+                Score,
+            ));
+            row.spawn((
+                TextBundle::from_section(
+                    lives_label(&game_mode, settings.lives.max(1), &locale),
+                    TextStyle {
+                        font: fonts.ui.clone(),
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_background_color(Color::BLACK)
+                .with_style(Style {
+                    // Roughly the gap below the score's digits, so both sit on one line.
+                    margin: UiRect::bottom(Val::Px(15.0)),
+                    ..default()
+                }),
+                Lives,
+            ));
+        });
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn score_and_spawn_new_circles(
     mut commands: Commands,
     mut next_state: ResMut<NextState<AppState>>,
     colors: Res<ColorResource>,
+    locale: Res<Locale>,
     mesh: Res<MeshResource>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    mut trial_log: ResMut<TrialLog>,
+    voice_clips: Option<Res<VoiceClips>>,
     mut circle_query: Query<(Entity, &mut Handle<ColorMaterial>), Without<Interactable>>,
     window_query: Query<&Window>,
     mut colored_word_query: Query<
@@ -190,56 +405,80 @@ fn score_and_spawn_new_circles(
         ),
     >,
 ) {
-    let mut rng = thread_rng();
+    let mut rng = &mut game_rng.rng;
 
     let window = window_query.single();
     let window_height = window.height();
 
-    let correct_or_incorrect: CorrectIncorrect =
-        CORRECT_OR_INCORRECT.choose(&mut rng).unwrap().clone();
-
-    let mut color_candidates = colors.0.to_vec().clone();
-
-    let correct_num = rng.gen_range(0..5);
-    let (correct_color, correct_color_name, correct_color_const) =
-        color_candidates.remove(correct_num);
-    let wrong_num = rng.gen_range(0..4);
-    let (wrong_color, wrong_color_name, wrong_color_const) =
-        color_candidates.remove(wrong_num).clone();
+    let mut color_candidates = colors.0.clone();
+    color_candidates.truncate(settings.stimulus.max_colors());
+
+    let correct_num = rng.gen_range(0..color_candidates.len());
+    let correct = color_candidates.remove(correct_num);
+    let wrong_num = rng.gen_range(0..color_candidates.len());
+    let wrong = color_candidates.remove(wrong_num);
+    // A two color palette has nothing left over for the background and the distractors, so
+    // they fall back to the incorrect color.
+    if color_candidates.is_empty() {
+        color_candidates.push(wrong.clone());
+    }
 
-    let word;
-    let word_color;
-    let word_or_color;
+    let stimulus = match settings.stimulus {
+        Stimulus::Visual | Stimulus::Auditory => color_word_trial(
+            &settings,
+            game_mode.congruent_ratio(),
+            &locale,
+            voice_clips.as_deref(),
+            (&correct, &wrong),
+            &mut rng,
+        ),
+        Stimulus::Emotional => emotional_trial(&settings.emotional_words, &correct, &mut rng),
+        Stimulus::Counting => counting_trial(
+            &settings.counting,
+            game_mode.congruent_ratio(),
+            &colors,
+            (&correct, &wrong),
+            &mut rng,
+        ),
+    };
 
-    match correct_or_incorrect {
-        CorrectIncorrect::Correct => {
-            word = correct_color_name.clone();
-            word_color = wrong_color_const.clone();
-            word_or_color = "As Written".to_string();
-        }
-        CorrectIncorrect::Incorrect => {
-            word = wrong_color_name.clone();
-            word_color = correct_color_const.clone();
-            word_or_color = "As Colored".to_string();
-        }
+    let mut colored_word = colored_word_query.single_mut();
+    let mut colored_or_word = colored_or_word_query.single_mut();
+    colored_word.sections[0].value = stimulus.text;
+    colored_word.sections[0].style.color = stimulus.color;
+    colored_or_word.sections[0].value = locale.get(stimulus.rule_key);
+    if let Some(spoken) = stimulus.spoken {
+        commands.spawn((spoken, StateScoped(TARGET_STATES)));
     }
 
-    let mut colored_word = colored_word_query.single_mut();
-    colored_word.sections[0].value = word;
-    colored_word.sections[0].style.color = word_color;
+    trial_log.trials.push(Trial {
+        category: stimulus.category,
+        ..Trial::new(
+            stimulus.word,
+            stimulus.ink,
+            stimulus.rule.to_string(),
+            stimulus.congruency,
+            difficulty.target_speed,
+            time.elapsed_seconds_f64(),
+        )
+    });
 
-    let mut colored_or_word = colored_or_word_query.single_mut();
-    colored_or_word.sections[0].value = word_or_color;
+    // The upper and lower bands start out spanning the screen and close in on the middle as
+    // the difficulty rises.
+    let middle = window_height / 2.;
+    let band_height = ((middle - 180.) * difficulty.separation(&settings)).max(1.);
+    let upper_band = (middle + 20.)..(middle + 20. + band_height);
+    let lower_band = (middle - 20. - band_height)..(middle - 20.);
 
     let correct_top: bool = rng.gen();
     let correct_y;
     let incorrect_y;
     if correct_top {
-        correct_y = rng.gen_range((window_height / 2. + 20.)..(window_height - 160.));
-        incorrect_y = rng.gen_range((160.)..(window_height / 2. - 20.));
+        correct_y = rng.gen_range(upper_band.clone());
+        incorrect_y = rng.gen_range(lower_band.clone());
     } else {
-        incorrect_y = rng.gen_range((window_height / 2. + 20.)..(window_height - 160.));
-        correct_y = rng.gen_range((160.)..(window_height / 2. - 20.));
+        incorrect_y = rng.gen_range(upper_band.clone());
+        correct_y = rng.gen_range(lower_band.clone());
     }
 
     circle_query.iter_mut().for_each(|(_, mut color)| {
@@ -251,26 +490,59 @@ fn score_and_spawn_new_circles(
     commands
         .spawn(MaterialMesh2dBundle {
             mesh: mesh.0.clone(),
-            material: correct_color.clone(),
-            transform: Transform::from_translation(Vec3::new(-20., correct_y, 3.)),
+            material: correct.0.clone(),
+            transform: Transform::from_translation(Vec3::new(
+                -settings.circle_radius,
+                correct_y,
+                3.,
+            )),
             ..default()
         })
         .insert(Interactable)
         .insert(Correct)
-        .insert(Offset { 0: offset });
+        .insert(Offset(offset))
+        .insert(StateScoped(TARGET_STATES));
 
     commands
         .spawn(MaterialMesh2dBundle {
             mesh: mesh.0.clone(),
-            material: wrong_color.clone(),
-            transform: Transform::from_translation(Vec3::new(-20., incorrect_y, 3.)),
+            material: wrong.0.clone(),
+            transform: Transform::from_translation(Vec3::new(
+                -settings.circle_radius,
+                incorrect_y,
+                3.,
+            )),
             ..default()
         })
         .insert(Interactable)
         .insert(Incorrect)
-        .insert(Offset { 0: offset });
-
-    println!("Correct: {:?}", correct_color);
+        .insert(Offset(offset))
+        .insert(StateScoped(TARGET_STATES));
+
+    // Extra incorrect circles in the remaining colors, trailing the main pair.
+    for distractor in 0..difficulty.distractors(&settings) {
+        let (distractor_color, _, _) = color_candidates.choose(&mut rng).unwrap().clone();
+        let distractor_y = if rng.gen() {
+            rng.gen_range(upper_band.clone())
+        } else {
+            rng.gen_range(lower_band.clone())
+        };
+        commands
+            .spawn(MaterialMesh2dBundle {
+                mesh: mesh.0.clone(),
+                material: distractor_color,
+                transform: Transform::from_translation(Vec3::new(
+                    -settings.circle_radius * (3. + 2. * distractor as f32),
+                    distractor_y,
+                    3.,
+                )),
+                ..default()
+            })
+            .insert(Interactable)
+            .insert(Incorrect)
+            .insert(Offset(offset))
+            .insert(StateScoped(TARGET_STATES));
+    }
 
     next_state.set(AppState::InGame);
 }
@@ -279,11 +551,13 @@ fn move_target_circles(
     mut transform_query: Query<(&mut Transform, &Offset), With<Interactable>>,
     time: Res<Time>,
     mut missed_circle_event: EventWriter<MissedCircleEvent>,
+    settings: Res<GameSettings>,
+    difficulty: Res<Difficulty>,
     window_query: Query<&Window>,
 ) {
     let window = window_query.single();
-    let window_width = window.width() + 40.;
-    let delta_time = time.delta_seconds() * 200.;
+    let window_width = window.width() + (settings.circle_radius * 2.);
+    let delta_time = time.delta_seconds() * difficulty.target_speed;
     transform_query.iter_mut().for_each(|(mut transform, _)| {
         transform.translation.x += delta_time;
         if transform.translation.x >= window_width {
@@ -292,9 +566,20 @@ fn move_target_circles(
     });
 }
 
+// Everything the player can do, or fail to do, that answers the current trial, and word of how
+// it was resolved.
+#[derive(SystemParam)]
+struct Responses<'w, 's> {
+    clicks: EventReader<'w, 's, LeftClickEvent>,
+    colors: EventReader<'w, 's, ColorResponseEvent>,
+    missed: EventReader<'w, 's, MissedCircleEvent>,
+    resolved: EventWriter<'w, TrialResolvedEvent>,
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn process_events_and_timers(
-    correct_query: Query<(Entity, &Transform), With<Correct>>,
-    incorrect_query: Query<(Entity, &Transform), With<Incorrect>>,
+    correct_query: Query<(&Transform, &Handle<ColorMaterial>), With<Correct>>,
+    incorrect_query: Query<(&Transform, &Handle<ColorMaterial>), With<Incorrect>>,
     mut timer_query: Query<
         &mut Text,
         (
@@ -317,74 +602,306 @@ fn process_events_and_timers(
 
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    mut click_event: EventReader<LeftClickEvent>,
-    missed_event: EventReader<MissedCircleEvent>,
+    mut trial_log: ResMut<TrialLog>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    colors: Res<ColorResource>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+    mut practice: Option<ResMut<Practice>>,
+    mut responses: Responses,
     time: Res<Time>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    let (correct_entity, correct) = correct_query.single();
-    let (incorrect_entity, incorrect) = incorrect_query.single();
+    let (correct, correct_material) = correct_query.single();
+
     let mut timer_text = timer_query.single_mut();
     let mut score_text = score_query.single_mut();
 
-    game_state.remaining_time.tick(time.delta());
-    if game_state.remaining_time.just_finished() {
-        game_state.timer_expired = true;
-        next_state.set(AppState::GameOver);
+    if practice.is_none() {
+        game_state.remaining_time.tick(time.delta());
+        if game_state.remaining_time.just_finished() {
+            game_state.timer_expired = true;
+            next_state.set(AppState::GameOver);
+        }
     }
     timer_text.sections[0].value =
         format!("{}", game_state.remaining_time.remaining_secs().trunc());
 
-    if !missed_event.is_empty() {
+    let now = time.elapsed_seconds_f64();
+
+    // Read rather than peeked at, so a miss is only counted once.
+    let missed = (responses.missed.read().count() > 0).then_some(TrialResolvedEvent {
+        outcome: TrialOutcome::Missed,
+        position: None,
+        correct_material: correct_material.clone(),
+        wrong_circle: None,
+        points: 0,
+    });
+
+    let clicks = responses.clicks.read().flat_map(|event| {
+        let hit_correct = event.position.distance(correct.translation.xy()) < settings.hit_radius;
+        let hit_incorrect = incorrect_query
+            .iter()
+            .map(|(incorrect, material)| (incorrect.translation.xy(), material.clone()))
+            .find(|(incorrect, _)| event.position.distance(*incorrect) < settings.hit_radius);
+        [
+            hit_correct.then_some(TrialResolvedEvent {
+                outcome: TrialOutcome::Correct,
+                position: Some(event.position),
+                correct_material: correct_material.clone(),
+                wrong_circle: None,
+                points: 0,
+            }),
+            hit_incorrect.map(|incorrect| TrialResolvedEvent {
+                outcome: TrialOutcome::Wrong,
+                position: Some(event.position),
+                correct_material: correct_material.clone(),
+                wrong_circle: Some(incorrect),
+                points: 0,
+            }),
+        ]
+        .into_iter()
+        .flatten()
+    });
+
+    // Keyboard responses name a color instead of a circle, so any color other than the
+    // correct circle's counts as a wrong answer.
+    let color_responses = responses
+        .colors
+        .read()
+        .map(|event| match colors.get(event.color) {
+            Some((material, _, _)) if material == correct_material => TrialResolvedEvent {
+                outcome: TrialOutcome::Correct,
+                position: Some(correct.translation.xy()),
+                correct_material: correct_material.clone(),
+                wrong_circle: None,
+                points: 0,
+            },
+            picked => {
+                let incorrect = picked.and_then(|(material, _, _)| {
+                    incorrect_query
+                        .iter()
+                        .find(|(_, incorrect_material)| *incorrect_material == material)
+                        .map(|(incorrect, _)| (incorrect.translation.xy(), material.clone()))
+                });
+                TrialResolvedEvent {
+                    outcome: TrialOutcome::Wrong,
+                    position: incorrect.as_ref().map(|(position, _)| *position),
+                    correct_material: correct_material.clone(),
+                    wrong_circle: incorrect,
+                    points: 0,
+                }
+            }
+        });
+
+    // Only the first response counts, anything else that arrived in the same frame, another
+    // click or a miss, would otherwise cost a second life or score the trial twice.
+    let response = missed
+        .into_iter()
+        .chain(clicks)
+        .chain(color_responses)
+        .next();
+    responses.clicks.clear();
+    responses.colors.clear();
+    let Some(mut response) = response else {
+        return;
+    };
+
+    let outcome = response.outcome;
+    if let Some(trial) = trial_log.current_mut() {
+        trial.resolve(outcome, now);
+    }
+    if outcome == TrialOutcome::Correct && practice.is_none() {
+        response.points = 1;
+    }
+    responses.resolved.send(response);
+
+    if let Some(practice) = practice.as_mut() {
+        let (message, color) = match outcome {
+            TrialOutcome::Correct => ("feedback.correct", Color::GREEN),
+            TrialOutcome::Missed => ("feedback.practice_missed", Color::RED),
+            _ => ("feedback.practice_wrong", Color::RED),
+        };
+        spawn_feedback(&mut commands, &fonts, &locale.get(message), color);
+        practice.completed += 1;
+        // The practice rounds are thrown away with the rest of the game state when the real
+        // game is set up.
+        next_state.set(if practice.completed >= practice.trials {
+            commands.remove_resource::<Practice>();
+            AppState::GameStart
+        } else {
+            AppState::NextRound
+        });
+        return;
+    }
+
+    if outcome == TrialOutcome::Correct {
+        game_state.score += 1;
+        score_text.sections[0].value = format!("{}", game_state.score);
+        next_state.set(AppState::NextRound);
+        return;
+    }
+
+    // Training never costs a life, the staircase slows the targets down instead.
+    if *game_mode != GameMode::Training {
+        game_state.lives = game_state.lives.saturating_sub(1);
+        let message = match outcome {
+            TrialOutcome::Missed => "feedback.missed",
+            _ => "feedback.wrong",
+        };
+        spawn_feedback(&mut commands, &fonts, &locale.get(message), Color::RED);
+    }
+    if game_state.lives == 0 {
         next_state.set(AppState::GameOver);
+    } else if !game_state.timer_expired {
+        next_state.set(AppState::NextRound);
     }
+}
 
-    for event in click_event.read() {
-        if event.position.distance(correct.translation.xy()) < 21. {
-            game_state.score += 1;
-            commands.entity(correct_entity).despawn();
-            commands.entity(incorrect_entity).despawn();
-            score_text.sections[0].value = format!("{}", game_state.score);
-            next_state.set(AppState::NextRound);
-        }
-        if event.position.distance(incorrect.translation.xy()) < 21. {
-            next_state.set(AppState::GameOver);
+fn spawn_feedback(commands: &mut Commands, fonts: &Fonts, message: &str, color: Color) {
+    commands.spawn((
+        TextBundle::from_section(
+            message,
+            TextStyle {
+                font: fonts.ui.clone(),
+                font_size: 60.0,
+                color,
+            },
+        )
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            align_self: AlignSelf::Center,
+            margin: UiRect::all(Val::Auto),
+            ..default()
+        }),
+        Feedback(Timer::from_seconds(1., TimerMode::Once)),
+        StateScoped(ROUND_STATES),
+    ));
+}
+
+fn fade_feedback(
+    mut commands: Commands,
+    mut feedback_query: Query<(Entity, &mut Feedback, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut feedback, mut text) in feedback_query.iter_mut() {
+        feedback.0.tick(time.delta());
+        if feedback.0.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            text.sections[0]
+                .style
+                .color
+                .set_a(feedback.0.percent_left());
         }
     }
 }
 
+// Training never takes a life, so there's nothing to count.
+fn lives_label(game_mode: &GameMode, lives: usize, locale: &Locale) -> String {
+    match game_mode {
+        GameMode::Training => String::new(),
+        _ => locale.format("hud.lives", &[("lives", &lives)]),
+    }
+}
+
+fn update_lives_text(
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    locale: Res<Locale>,
+    practice: Option<Res<Practice>>,
+    mut lives_query: Query<&mut Text, With<Lives>>,
+) {
+    let practice_changed = practice
+        .as_ref()
+        .is_some_and(|practice| practice.is_changed());
+    if !game_state.is_changed() && !practice_changed {
+        return;
+    }
+    for mut lives_text in lives_query.iter_mut() {
+        lives_text.sections[0].value = match &practice {
+            Some(practice) => locale.format(
+                "hud.practice",
+                &[
+                    ("trial", &(practice.completed + 1).min(practice.trials)),
+                    ("trials", &practice.trials),
+                ],
+            ),
+            None => lives_label(&game_mode, game_state.lives, &locale),
+        };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn game_over(
     mut commands: Commands,
     game_state: Res<GameState>,
-    text_boxes: Query<(Entity, &Text)>,
-    correct_query: Query<(Entity, &Transform), With<Correct>>,
-    incorrect_query: Query<(Entity, &Transform), With<Incorrect>>,
+    trial_log: Res<TrialLog>,
+    participant_id: Res<ParticipantId>,
+    game_mode: Res<GameMode>,
+    game_rng: Res<GameRng>,
+    staircase: Res<Staircase>,
+    settings: Res<GameSettings>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
 ) {
-    for (text_box, _) in text_boxes.iter() {
-        commands.entity(text_box).despawn();
+    let categories = match settings.stimulus {
+        Stimulus::Emotional => CategorySummary::all(&trial_log, &settings.emotional_words),
+        _ => Vec::new(),
+    };
+    let results = SessionResults::new(
+        &participant_id,
+        game_mode.name(),
+        settings.stimulus,
+        locale.stimulus_language.clone(),
+        game_rng.seed,
+        game_state.score,
+        game_state.timer_expired,
+        &trial_log,
+        (*game_mode == GameMode::Training)
+            .then(|| StaircaseSummary::new(&staircase, &settings.staircase)),
+        categories,
+    );
+    match results.export(&settings.results_dir) {
+        Ok((csv_path, json_path)) => {
+            info!("Session saved to {:?} and {:?}", csv_path, json_path)
+        }
+        Err(err) => error!("Failed to save session results: {}", err),
     }
 
-    for (circle, _) in correct_query.iter() {
-        commands.entity(circle).despawn();
+    let mut lines = vec![
+        locale.get(if game_state.timer_expired {
+            "game_over.won"
+        } else {
+            "game_over.lost"
+        }),
+        locale.format("game_over.score", &[("score", &game_state.score)]),
+    ];
+    if let Some(effect) = results.stroop_effect_ms {
+        lines.push(locale.format("game_over.stroop_effect", &[("ms", &effect.round())]));
     }
-
-    for (circle, _) in incorrect_query.iter() {
-        commands.entity(circle).despawn();
+    for summary in &results.categories {
+        if let Some(bias) = summary.bias_ms {
+            lines.push(locale.format(
+                "game_over.bias",
+                &[("category", &summary.category), ("ms", &bias.round())],
+            ));
+        }
     }
-
-    let game_over_text;
-
-    if game_state.timer_expired {
-        game_over_text = format!(
-            "Congratulations! You won!\nScore: {}\n\nPress Space to start or Esc to quit",
-            game_state.score
-        );
-    } else {
-        game_over_text = format!(
-            "Sorry. You lost!\nScore: {}\n\nPress Space to start or Esc to quit",
-            game_state.score
-        );
+    if let Some(threshold) = results
+        .staircase
+        .as_ref()
+        .and_then(|staircase| staircase.threshold_speed)
+    {
+        lines.push(locale.format("game_over.threshold", &[("speed", &threshold.round())]));
     }
+    lines.push(String::new());
+    lines.push(locale.get("game_over.prompt"));
+    commands.insert_resource(results);
+
+    let game_over_text = lines.join("\n");
 
     commands.spawn((
         // Create a TextBundle that has a Text with a single section.
@@ -392,10 +909,9 @@ fn game_over(
             // Accepts a `String` or any type that converts into a `String`, such as `&str`
             game_over_text,
             TextStyle {
-                // This font is loaded and will be used instead of the default font.
+                font: fonts.ui.clone(),
                 font_size: 100.0,
                 color: Color::WHITE,
-                ..default()
             },
         ) // Set the justification of the Text
         .with_background_color(Color::BLACK)
@@ -409,20 +925,19 @@ fn game_over(
             ..default()
         }),
         GameOverText,
+        StateScoped(&[AppState::GameOver]),
     ));
 }
 
 fn game_over_input(
-    mut commands: Commands,
     mut next_state: ResMut<NextState<AppState>>,
     mut exit: EventWriter<AppExit>,
     keyboard_input: Res<Input<KeyCode>>,
-    text_boxes: Query<(Entity, &Text)>,
+    prompt_query: Query<(), With<HighScorePrompt>>,
 ) {
-    if keyboard_input.pressed(KeyCode::Space) || keyboard_input.pressed(KeyCode::Return) {
-        for (text_box, _) in text_boxes.iter() {
-            commands.entity(text_box).despawn();
-        }
+    // Enter is left to the leaderboard while there's a high score to save.
+    let enter = keyboard_input.pressed(KeyCode::Return) && prompt_query.is_empty();
+    if keyboard_input.pressed(KeyCode::Space) || enter {
         next_state.set(AppState::GameStart)
     }
 
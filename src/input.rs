@@ -1,4 +1,5 @@
 use crate::*;
+use bevy::input::InputSystem;
 use bevy::prelude::*;
 
 pub struct InputPlugin;
@@ -8,17 +9,188 @@ pub struct LeftClickEvent {
     pub position: Vec2,
 }
 
+// Sent in keyboard response mode with the index into `ColorResource` of the chosen color.
+#[derive(Event)]
+pub struct ColorResponseEvent {
+    pub color: usize,
+}
+
+const NUMBER_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreUpdate, cursor_system.run_if(in_state(AppState::InGame)))
-            .add_event::<LeftClickEvent>();
+        app.add_systems(
+            PreUpdate,
+            (cursor_system, keyboard_response_system)
+                .after(InputSystem)
+                .run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(Update, label_answer_keys)
+        .add_event::<LeftClickEvent>()
+        .add_event::<ColorResponseEvent>();
+    }
+}
+
+pub fn letter_key(letter: char) -> Option<KeyCode> {
+    let key = match letter.to_ascii_uppercase() {
+        'A' => KeyCode::A,
+        'B' => KeyCode::B,
+        'C' => KeyCode::C,
+        'D' => KeyCode::D,
+        'E' => KeyCode::E,
+        'F' => KeyCode::F,
+        'G' => KeyCode::G,
+        'H' => KeyCode::H,
+        'I' => KeyCode::I,
+        'J' => KeyCode::J,
+        'K' => KeyCode::K,
+        'L' => KeyCode::L,
+        'M' => KeyCode::M,
+        'N' => KeyCode::N,
+        'O' => KeyCode::O,
+        'P' => KeyCode::P,
+        'Q' => KeyCode::Q,
+        'R' => KeyCode::R,
+        'S' => KeyCode::S,
+        'T' => KeyCode::T,
+        'U' => KeyCode::U,
+        'V' => KeyCode::V,
+        'W' => KeyCode::W,
+        'X' => KeyCode::X,
+        'Y' => KeyCode::Y,
+        'Z' => KeyCode::Z,
+        _ => return None,
+    };
+    Some(key)
+}
+
+// The letter each color answers to: its configured key, or else the first letter of its name in
+// the UI language that isn't taken yet, so German ROT, GELB and GRÜN get R, G and N. Counting
+// sessions answer with the number keys only, the count isn't a color.
+pub fn answer_letters(settings: &GameSettings, locale: &Locale) -> Vec<Option<char>> {
+    let palette = &settings.palette;
+    if settings.stimulus == Stimulus::Counting {
+        return vec![None; palette.len()];
+    }
+    let mut taken: Vec<char> = palette
+        .iter()
+        .filter_map(|color| color.key)
+        .map(|key| key.to_ascii_uppercase())
+        .collect();
+    palette
+        .iter()
+        .map(|color| {
+            color.key.map(|key| key.to_ascii_uppercase()).or_else(|| {
+                let letter = locale
+                    .color_name(&color.name)
+                    .chars()
+                    .map(|letter| letter.to_ascii_uppercase())
+                    .find(|letter| letter_key(*letter).is_some() && !taken.contains(letter))?;
+                taken.push(letter);
+                Some(letter)
+            })
+        })
+        .collect()
+}
+
+// Each color answers to its number key (1 for the first palette entry and so on, up to 9) and
+// to its letter from `answer_letters`, so the default palette is R/Y/G/B/P in English.
+pub fn color_keys(index: usize, letter: Option<char>) -> Vec<KeyCode> {
+    NUMBER_KEYS
+        .get(index)
+        .copied()
+        .into_iter()
+        .chain(letter.and_then(letter_key))
+        .collect()
+}
+
+// The key beside a target circle in keyboard response mode.
+#[derive(Component)]
+pub struct KeyLabel;
+
+// Each target circle shows the key that answers its color, the letters aren't always the
+// first letter of the color's name.
+fn label_answer_keys(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    colors: Res<ColorResource>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+    circle_query: Query<(Entity, &Handle<ColorMaterial>), Added<Interactable>>,
+) {
+    // Counting circles already show their number.
+    if settings.response_mode != ResponseMode::Keyboard || settings.stimulus == Stimulus::Counting {
+        return;
+    }
+    let letters = answer_letters(&settings, &locale);
+    for (circle, material) in circle_query.iter() {
+        let Some(index) = colors.iter().position(|(color, _, _)| color == material) else {
+            continue;
+        };
+        let label = match letters[index] {
+            Some(letter) => letter.to_string(),
+            None => (index + 1).to_string(),
+        };
+        commands.entity(circle).with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        label,
+                        TextStyle {
+                            font: fonts.ui.clone(),
+                            font_size: settings.circle_radius,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    transform: Transform::from_xyz(settings.circle_radius * 1.8, 0., 1.),
+                    ..default()
+                },
+                KeyLabel,
+            ));
+        });
+    }
+}
+
+fn keyboard_response_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    settings: Res<GameSettings>,
+    locale: Res<Locale>,
+    mut color_response: EventWriter<ColorResponseEvent>,
+) {
+    if settings.response_mode != ResponseMode::Keyboard {
+        return;
+    }
+
+    if keyboard_input.get_just_pressed().next().is_none() {
+        return;
+    }
+
+    let letters = answer_letters(&settings, &locale);
+    if let Some(color) = keyboard_input.get_just_pressed().find_map(|key| {
+        letters
+            .iter()
+            .enumerate()
+            .position(|(index, letter)| color_keys(index, *letter).contains(key))
+    }) {
+        color_response.send(ColorResponseEvent { color });
     }
 }
 
 fn cursor_system(
     mut next_state: ResMut<NextState<AppState>>,
+    settings: Res<GameSettings>,
     btn: Res<Input<MouseButton>>,
-    keyboard_input: Res<Input<KeyCode>>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     window_query: Query<&Window>,
     mut left_click: EventWriter<LeftClickEvent>,
@@ -26,7 +198,7 @@ fn cursor_system(
     let (camera, camera_transform) = camera_query.single();
     let window = window_query.get_single().unwrap();
 
-    if btn.just_pressed(MouseButton::Left) {
+    if settings.response_mode == ResponseMode::Mouse && btn.just_pressed(MouseButton::Left) {
         if let Some(world_position) = window
             .cursor_position()
             .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
@@ -43,7 +215,9 @@ fn cursor_system(
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        next_state.set(AppState::Menu);
+    // The pause screen reads Escape too, and the state changes before it runs this frame, so
+    // the press is consumed here rather than immediately resuming.
+    if keyboard_input.clear_just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::Paused);
     }
 }
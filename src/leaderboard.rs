@@ -0,0 +1,304 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+const MAX_NAME_LENGTH: usize = 12;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: usize,
+    pub date: String,
+    pub mode: String,
+}
+
+// Highest score first, never longer than `GameSettings::leaderboard_size`.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    // `leaderboard.json` inside `GameSettings::data_dir`.
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("leaderboard.json")
+    }
+
+    pub fn load(dir: &Path) -> Self {
+        match fs::read_to_string(Self::path(dir)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Ignoring unreadable leaderboard: {}", err);
+                Leaderboard::default()
+            }),
+            Err(_) => Leaderboard::default(),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path(dir);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn qualifies(&self, score: usize, size: usize) -> bool {
+        score > 0
+            && (self.entries.len() < size || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    pub fn insert(&mut self, entry: LeaderboardEntry, size: usize) {
+        // Ties go below the existing entries so the earlier score keeps its place.
+        let position = self
+            .entries
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+        self.entries.truncate(size);
+    }
+}
+
+#[derive(Resource, Default)]
+struct PlayerName(String);
+
+// Shown on the game over screen while Enter leads to name entry rather than a new game.
+#[derive(Component)]
+pub struct HighScorePrompt;
+
+#[derive(Component)]
+struct NameEntryText;
+
+#[derive(Component)]
+struct LeaderboardText;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Leaderboard>()
+            .init_resource::<PlayerName>()
+            .add_systems(Startup, load_leaderboard)
+            .add_systems(OnEnter(AppState::GameOver), high_score_prompt)
+            .add_systems(
+                Update,
+                high_score_input.run_if(in_state(AppState::GameOver)),
+            )
+            .add_systems(OnEnter(AppState::NameEntry), name_entry_setup)
+            .add_systems(Update, name_entry.run_if(in_state(AppState::NameEntry)))
+            .add_systems(OnEnter(AppState::Leaderboard), leaderboard_setup)
+            .add_systems(
+                Update,
+                leaderboard_input.run_if(in_state(AppState::Leaderboard)),
+            );
+    }
+}
+
+// Waits for the settings, which say where the leaderboard is kept.
+fn load_leaderboard(mut commands: Commands, settings: Res<GameSettings>) {
+    commands.insert_resource(Leaderboard::load(&settings.data_dir()));
+}
+
+fn high_score_prompt(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    game_state: Res<GameState>,
+    settings: Res<GameSettings>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    if !leaderboard.qualifies(game_state.score(), settings.leaderboard_size) {
+        return;
+    }
+
+    commands.spawn((
+        TextBundle::from_section(
+            locale.get("leaderboard.new_high_score"),
+            TextStyle {
+                font: fonts.ui.clone(),
+                font_size: 40.0,
+                color: Color::YELLOW,
+            },
+        )
+        .with_background_color(Color::BLACK)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            align_self: AlignSelf::Center,
+            margin: UiRect::horizontal(Val::Auto),
+            ..default()
+        }),
+        HighScorePrompt,
+        StateScoped(&[AppState::GameOver]),
+    ));
+}
+
+fn high_score_input(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    prompt_query: Query<&HighScorePrompt>,
+) {
+    if prompt_query.is_empty() || !keyboard_input.just_pressed(KeyCode::Return) {
+        return;
+    }
+
+    next_state.set(AppState::NameEntry);
+}
+
+fn name_entry_setup(
+    mut commands: Commands,
+    mut player_name: ResMut<PlayerName>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    player_name.0.clear();
+
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                format!("{}\n", locale.get("leaderboard.enter_name")),
+                TextStyle {
+                    font: fonts.ui.clone(),
+                    font_size: 60.0,
+                    color: Color::WHITE,
+                },
+            ),
+            TextSection::new(
+                "_",
+                TextStyle {
+                    font: fonts.ui.clone(),
+                    font_size: 80.0,
+                    color: Color::YELLOW,
+                },
+            ),
+            TextSection::new(
+                format!("\n\n{}", locale.get("leaderboard.name_prompt")),
+                TextStyle {
+                    font: fonts.ui.clone(),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            ),
+        ])
+        .with_background_color(Color::BLACK)
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            align_items: AlignItems::Center,
+            align_self: AlignSelf::Center,
+            margin: UiRect::all(Val::Auto),
+            ..default()
+        }),
+        NameEntryText,
+        StateScoped(&[AppState::NameEntry]),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn name_entry(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut player_name: ResMut<PlayerName>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keyboard_input: Res<Input<KeyCode>>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    settings: Res<GameSettings>,
+    mut name_text_query: Query<&mut Text, With<NameEntryText>>,
+) {
+    for event in characters.read() {
+        if !event.char.is_control() && player_name.0.chars().count() < MAX_NAME_LENGTH {
+            player_name.0.push(event.char);
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        player_name.0.pop();
+    }
+
+    let mut name_text = name_text_query.single_mut();
+    name_text.sections[1].value = format!("{}_", player_name.0);
+
+    let save = keyboard_input.just_pressed(KeyCode::Return) && !player_name.0.trim().is_empty();
+    if save {
+        leaderboard.insert(
+            LeaderboardEntry {
+                name: player_name.0.trim().to_string(),
+                score: game_state.score(),
+                date: Local::now().format("%Y-%m-%d").to_string(),
+                mode: game_mode.name(),
+            },
+            settings.leaderboard_size,
+        );
+        if let Err(err) = leaderboard.save(&settings.data_dir()) {
+            error!("Failed to save leaderboard: {}", err);
+        }
+    }
+
+    if save || keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(if save {
+            AppState::Leaderboard
+        } else {
+            AppState::Menu
+        });
+    }
+}
+
+fn leaderboard_setup(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    let mut table = format!("{}\n\n", locale.get("leaderboard.title"));
+    if leaderboard.entries.is_empty() {
+        table.push_str(&format!("{}\n", locale.get("leaderboard.empty")));
+    }
+    for (rank, entry) in leaderboard.entries.iter().enumerate() {
+        table.push_str(&format!(
+            "{:>2}. {:<12} {:>4}  {}  {}\n",
+            rank + 1,
+            entry.name,
+            entry.score,
+            entry.date,
+            entry.mode
+        ));
+    }
+    table.push_str(&format!("\n{}", locale.get("leaderboard.return")));
+
+    commands.spawn((
+        TextBundle::from_section(
+            table,
+            TextStyle {
+                font: fonts.ui.clone(),
+                font_size: 40.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_background_color(Color::BLACK)
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            align_items: AlignItems::Center,
+            align_self: AlignSelf::Center,
+            margin: UiRect::all(Val::Auto),
+            ..default()
+        }),
+        LeaderboardText,
+        StateScoped(&[AppState::Leaderboard]),
+    ));
+}
+
+fn leaderboard_input(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+) {
+    if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Return, KeyCode::Escape]) {
+        next_state.set(AppState::Menu);
+    }
+}
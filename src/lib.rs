@@ -0,0 +1,92 @@
+mod audio;
+mod auditory;
+mod color_vision;
+mod components;
+mod counting;
+mod difficulty;
+mod emotional;
+mod feedback;
+mod fonts;
+mod gameplay;
+mod input;
+mod leaderboard;
+mod locale;
+mod menu;
+mod pause;
+mod rng;
+mod session;
+mod settings;
+mod staircase;
+mod state_scope;
+mod stimulus;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+pub use crate::audio::*;
+pub use crate::auditory::*;
+pub use crate::color_vision::*;
+pub use crate::components::*;
+pub use crate::counting::*;
+pub use crate::difficulty::*;
+pub use crate::emotional::*;
+pub use crate::feedback::*;
+pub use crate::fonts::*;
+pub use crate::gameplay::*;
+pub use crate::input::*;
+pub use crate::leaderboard::*;
+pub use crate::locale::*;
+pub use crate::menu::*;
+pub use crate::pause::*;
+pub use crate::rng::*;
+pub use crate::session::*;
+pub use crate::settings::*;
+pub use crate::staircase::*;
+pub use crate::state_scope::*;
+pub use crate::stimulus::*;
+
+pub const NUMBER_ENTITIES: usize = 10_000;
+pub const COLOR_SELECTION: [Color; 5] = [
+    Color::RED,
+    Color::YELLOW,
+    Color::GREEN,
+    Color::BLUE,
+    Color::PURPLE,
+];
+pub const CIRCLE_RADIUS: f32 = 20.;
+pub const BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.4, 0.4, 0.4);
+
+#[derive(Resource, Deref, DerefMut, Clone)]
+// One entry per `GameSettings::palette` color, in the same order.
+pub struct ColorResource(pub Vec<(Handle<ColorMaterial>, String, Color)>);
+
+#[derive(Resource, Deref, DerefMut, Clone)]
+pub struct MeshResource(pub Mesh2dHandle);
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Settings,
+    Instructions,
+    GameStart,
+    InGame,
+    Paused,
+    NextRound,
+    GameOver,
+    NameEntry,
+    Leaderboard,
+}
+
+// Returns the value following `flag` on the command line, e.g. `--participant P01`.
+pub fn cli_arg(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+    }
+    None
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+
+pub const LOCALES_DIR: &str = "assets/locales";
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+const ENGLISH: &str = include_str!("../assets/locales/en.ron");
+
+// One language's strings, keyed like `menu.play` or `color.RED`.
+#[derive(Clone, Debug, Default)]
+pub struct StringTable(HashMap<String, String>);
+
+impl StringTable {
+    pub fn english() -> Self {
+        StringTable(ron::from_str(ENGLISH).expect("built-in English strings are valid"))
+    }
+
+    // Reads `<dir>/<language>.ron`.
+    pub fn load(dir: &Path, language: &str) -> Result<Self, String> {
+        let path = dir.join(format!("{}.ron", language));
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        ron::from_str(&contents)
+            .map(StringTable)
+            .map_err(|err| format!("Invalid strings in {}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+// The UI language and, separately, the language of the color words used as stimuli, so a
+// bilingual Stroop can show German words under English instructions. Anything a table leaves
+// out falls back to English, and palette colors without a translation keep their own name.
+#[derive(Resource, Clone, Debug)]
+pub struct Locale {
+    pub language: String,
+    pub stimulus_language: String,
+    ui: StringTable,
+    stimulus: StringTable,
+    fallback: StringTable,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale {
+            language: DEFAULT_LANGUAGE.to_string(),
+            stimulus_language: DEFAULT_LANGUAGE.to_string(),
+            ui: StringTable::default(),
+            stimulus: StringTable::default(),
+            fallback: StringTable::english(),
+        }
+    }
+}
+
+impl Locale {
+    pub fn new(dir: &Path, language: &str, stimulus_language: &str) -> Result<Self, String> {
+        let load = |language: &str| {
+            if language == DEFAULT_LANGUAGE {
+                Ok(StringTable::default())
+            } else {
+                StringTable::load(dir, language)
+            }
+        };
+        Ok(Locale {
+            language: language.to_string(),
+            stimulus_language: stimulus_language.to_string(),
+            ui: load(language)?,
+            stimulus: load(stimulus_language)?,
+            fallback: StringTable::english(),
+        })
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.ui
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    // `get` with every `{name}` placeholder replaced by its value.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter().fold(self.get(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+
+    // A palette color's name in the UI language, e.g. for "Answer: GREEN".
+    pub fn color_name(&self, name: &str) -> String {
+        let key = format!("color.{}", name);
+        self.ui
+            .get(&key)
+            .or_else(|| self.fallback.get(&key))
+            .unwrap_or(name)
+            .to_string()
+    }
+
+    // A palette color's name in the stimulus language, the word the player has to read.
+    pub fn stimulus_word(&self, name: &str) -> String {
+        let key = format!("color.{}", name);
+        self.stimulus
+            .get(&key)
+            .or_else(|| self.fallback.get(&key))
+            .unwrap_or(name)
+            .to_string()
+    }
+}
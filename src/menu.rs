@@ -0,0 +1,618 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::*;
+
+const MAX_LIVES: usize = 5;
+const ROUND_LENGTHS: [f32; 4] = [30., 60., 90., 120.];
+const VOLUME_LEVELS: [f32; 5] = [0., 0.25, 0.5, 0.75, 1.];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuAction {
+    Play,
+    CycleMode,
+    Settings,
+    Leaderboard,
+    Instructions,
+    Quit,
+    CycleResponseMode,
+    CycleLives,
+    CycleRoundLength,
+    CycleMasterVolume,
+    CycleMusicVolume,
+    CycleSfxVolume,
+    Practice,
+    StartGame,
+    Back,
+}
+
+const MAIN_MENU: [MenuAction; 6] = [
+    MenuAction::Play,
+    MenuAction::CycleMode,
+    MenuAction::Settings,
+    MenuAction::Leaderboard,
+    MenuAction::Instructions,
+    MenuAction::Quit,
+];
+
+const SETTINGS_MENU: [MenuAction; 7] = [
+    MenuAction::CycleResponseMode,
+    MenuAction::CycleLives,
+    MenuAction::CycleRoundLength,
+    MenuAction::CycleMasterVolume,
+    MenuAction::CycleMusicVolume,
+    MenuAction::CycleSfxVolume,
+    MenuAction::Back,
+];
+
+const INSTRUCTIONS_MENU: [MenuAction; 3] = [
+    MenuAction::Practice,
+    MenuAction::StartGame,
+    MenuAction::Back,
+];
+
+// How long each worked example stays up, the answer appears half way through.
+const EXAMPLE_SECONDS: f32 = 4.;
+
+#[derive(Component)]
+pub struct MenuButton {
+    pub index: usize,
+    pub action: MenuAction,
+}
+
+#[derive(Component)]
+struct ButtonLabel(MenuAction);
+
+// The button that Enter activates, moved with the arrow keys or by hovering with the mouse.
+#[derive(Resource, Default)]
+pub struct MenuSelection(pub usize);
+
+// Set once the instructions have been on screen, after which Play goes straight into a game.
+#[derive(Resource, Default)]
+pub struct InstructionsShown(pub bool);
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuSelection>()
+            .init_resource::<InstructionsShown>()
+            .add_systems(OnEnter(AppState::Menu), main_menu_setup)
+            .add_systems(OnEnter(AppState::Settings), settings_menu_setup)
+            .add_systems(OnEnter(AppState::Instructions), instructions_setup)
+            .add_systems(
+                Update,
+                animate_examples.run_if(in_state(AppState::Instructions)),
+            )
+            .add_systems(
+                Update,
+                show_invalid_settings.run_if(resource_added::<InvalidSettings>()),
+            )
+            .add_systems(
+                Update,
+                (menu_navigation, update_button_labels).chain().run_if(
+                    in_state(AppState::Menu)
+                        .or_else(in_state(AppState::Settings))
+                        .or_else(in_state(AppState::Instructions)),
+                ),
+            );
+    }
+}
+
+fn label(
+    action: MenuAction,
+    settings: &GameSettings,
+    game_mode: &GameMode,
+    volumes: &AudioVolumes,
+    locale: &Locale,
+) -> String {
+    let volume_label =
+        |key: &str, volume: f32| locale.format(key, &[("percent", &(volume * 100.).round())]);
+    match action {
+        MenuAction::Play => locale.get("menu.play"),
+        MenuAction::CycleMode => locale.format(
+            "menu.mode",
+            &[(
+                "mode",
+                &locale.get(&format!("mode.{}", game_mode.name().to_lowercase())),
+            )],
+        ),
+        MenuAction::Settings => locale.get("menu.settings"),
+        MenuAction::Leaderboard => locale.get("menu.leaderboard"),
+        MenuAction::Instructions => locale.get("menu.instructions"),
+        MenuAction::Quit => locale.get("menu.quit"),
+        MenuAction::CycleResponseMode => {
+            let mode = match settings.response_mode {
+                ResponseMode::Mouse => locale.get("response.mouse"),
+                ResponseMode::Keyboard => locale.get("response.keyboard"),
+            };
+            locale.format("settings.response_mode", &[("mode", &mode)])
+        }
+        MenuAction::CycleLives => locale.format("settings.lives", &[("lives", &settings.lives)]),
+        MenuAction::CycleRoundLength => locale.format(
+            "settings.round_length",
+            &[("seconds", &settings.round_seconds)],
+        ),
+        MenuAction::CycleMasterVolume => volume_label("settings.master_volume", volumes.master),
+        MenuAction::CycleMusicVolume => volume_label("settings.music_volume", volumes.music),
+        MenuAction::CycleSfxVolume => volume_label("settings.sfx_volume", volumes.sfx),
+        MenuAction::Practice => locale.format(
+            "instructions.practice",
+            &[("rounds", &settings.practice_trials.max(1))],
+        ),
+        MenuAction::StartGame => locale.get("instructions.start"),
+        MenuAction::Back => locale.get("menu.back"),
+    }
+}
+
+// Lays out a title, an optional paragraph and a column of buttons in the middle of the screen,
+// all belonging to `state`.
+#[allow(clippy::too_many_arguments)]
+fn spawn_menu(
+    commands: &mut Commands,
+    state: &'static [AppState],
+    title: &str,
+    body: Option<String>,
+    actions: &[MenuAction],
+    settings: &GameSettings,
+    game_mode: &GameMode,
+    volumes: &AudioVolumes,
+    locale: &Locale,
+    fonts: &Fonts,
+) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(state),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        font: fonts.ui.clone(),
+                        font_size: 80.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                }),
+                MenuText,
+            ));
+
+            if let Some(body) = body {
+                parent.spawn(
+                    TextBundle::from_section(
+                        body,
+                        TextStyle {
+                            font: fonts.ui.clone(),
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_text_alignment(TextAlignment::Center)
+                    .with_style(Style {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            for (index, action) in actions.iter().enumerate() {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(400.0),
+                                height: Val::Px(65.0),
+                                margin: UiRect::all(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        MenuButton {
+                            index,
+                            action: *action,
+                        },
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                label(*action, settings, game_mode, volumes, locale),
+                                TextStyle {
+                                    font: fonts.ui.clone(),
+                                    font_size: 40.0,
+                                    color: Color::WHITE,
+                                },
+                            ),
+                            ButtonLabel(*action),
+                        ));
+                    });
+            }
+
+            parent.spawn(TextBundle::from_section(
+                locale.get("menu.hint"),
+                TextStyle {
+                    font: fonts.ui.clone(),
+                    font_size: 24.0,
+                    color: Color::GRAY,
+                },
+            ));
+        })
+        .id()
+}
+
+// A reason no game can start, left on screen for the rest of the session.
+pub fn spawn_error(commands: &mut Commands, fonts: &Fonts, message: String) {
+    commands.spawn(
+        TextBundle::from_section(
+            message,
+            TextStyle {
+                font: fonts.ui.clone(),
+                font_size: 30.0,
+                color: Color::RED,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            right: Val::Px(20.0),
+            ..default()
+        }),
+    );
+}
+
+fn show_invalid_settings(
+    mut commands: Commands,
+    invalid: Res<InvalidSettings>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    spawn_error(
+        &mut commands,
+        &fonts,
+        locale.format("error.invalid_settings", &[("error", &invalid.0)]),
+    );
+}
+
+fn main_menu_setup(
+    mut commands: Commands,
+    mut selection: ResMut<MenuSelection>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    volumes: Res<AudioVolumes>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    selection.0 = 0;
+    // Quitting part way through a practice block drops the rest of it.
+    commands.remove_resource::<Practice>();
+    let menu = spawn_menu(
+        &mut commands,
+        &[AppState::Menu],
+        &locale.get("menu.title"),
+        None,
+        &MAIN_MENU,
+        &settings,
+        &game_mode,
+        &volumes,
+        &locale,
+        &fonts,
+    );
+    commands.entity(menu).insert(Menu);
+}
+
+fn settings_menu_setup(
+    mut commands: Commands,
+    mut selection: ResMut<MenuSelection>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    volumes: Res<AudioVolumes>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    selection.0 = 0;
+    spawn_menu(
+        &mut commands,
+        &[AppState::Settings],
+        &locale.get("settings.title"),
+        Some(locale.get("settings.note")),
+        &SETTINGS_MENU,
+        &settings,
+        &game_mode,
+        &volumes,
+        &locale,
+        &fonts,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn instructions_setup(
+    mut commands: Commands,
+    mut selection: ResMut<MenuSelection>,
+    mut shown: ResMut<InstructionsShown>,
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    volumes: Res<AudioVolumes>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+) {
+    selection.0 = 0;
+    shown.0 = true;
+    let answer = match (settings.response_mode, settings.stimulus) {
+        (ResponseMode::Mouse, Stimulus::Counting) => locale.get("instructions.answer_count_mouse"),
+        (ResponseMode::Keyboard, Stimulus::Counting) => {
+            locale.get("instructions.answer_count_keyboard")
+        }
+        (ResponseMode::Mouse, _) => locale.get("instructions.answer_mouse"),
+        (ResponseMode::Keyboard, _) => locale.get("instructions.answer_keyboard"),
+    };
+    // Training never takes a life, its staircase slows the targets down instead.
+    let errors = locale.get(match *game_mode {
+        GameMode::Training => "instructions.errors_training",
+        _ => "instructions.errors",
+    });
+    let voices: Vec<String> = settings
+        .voices
+        .iter()
+        .zip(&settings.palette)
+        .map(|(voice, color)| format!("{}: {}", voice.name, locale.color_name(&color.name)))
+        .collect();
+    let body = locale.format(
+        match settings.stimulus {
+            Stimulus::Visual => "instructions.body",
+            Stimulus::Auditory => "instructions.body_auditory",
+            Stimulus::Emotional => "instructions.body_emotional",
+            Stimulus::Counting => "instructions.body_counting",
+        },
+        &[
+            ("voices", &voices.join(", ")),
+            ("answer", &answer),
+            ("errors", &errors),
+        ],
+    );
+    let menu = spawn_menu(
+        &mut commands,
+        &[AppState::Instructions],
+        &locale.get("instructions.title"),
+        Some(body),
+        &INSTRUCTIONS_MENU,
+        &settings,
+        &game_mode,
+        &volumes,
+        &locale,
+        &fonts,
+    );
+    // The worked examples show printed color words under both rules, so they only fit visual
+    // sessions.
+    if settings.stimulus != Stimulus::Visual {
+        return;
+    }
+
+    // The example word is shown the way it will be in the game.
+    let section = |font: &Handle<Font>, font_size: f32| {
+        TextSection::new(
+            "",
+            TextStyle {
+                font: font.clone(),
+                font_size,
+                color: Color::WHITE,
+            },
+        )
+    };
+    let example = commands
+        .spawn((
+            TextBundle::from_sections([
+                section(&fonts.ui, 40.0),
+                section(&fonts.stimulus, fonts.stimulus_size),
+                section(&fonts.ui, 40.0),
+            ])
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                margin: UiRect::bottom(Val::Px(20.0)),
+                ..default()
+            }),
+            Instructions,
+        ))
+        .id();
+    // Below the title and the explanation, above the buttons.
+    commands.entity(menu).insert_children(2, &[example]);
+}
+
+// Cycles through worked examples of both rules, each showing the rule and the word first and
+// revealing the answer a moment later.
+fn animate_examples(
+    time: Res<Time>,
+    colors: Res<ColorResource>,
+    locale: Res<Locale>,
+    mut elapsed: Local<f32>,
+    mut example_query: Query<&mut Text, With<Instructions>>,
+) {
+    *elapsed += time.delta_seconds();
+    let step = (*elapsed / EXAMPLE_SECONDS) as usize;
+    let reveal = (*elapsed % EXAMPLE_SECONDS) / (EXAMPLE_SECONDS / 2.) - 1.;
+
+    let count = colors.len();
+    let word = step / 2 % count;
+    let ink = (word + 1 + step / 2 / count % (count - 1)) % count;
+    let (rule, answer) = if step.is_multiple_of(2) {
+        (WordOrColor::Word, word)
+    } else {
+        (WordOrColor::Color, ink)
+    };
+
+    for mut text in example_query.iter_mut() {
+        text.sections[0].value = format!(
+            "{}\n",
+            locale.format("instructions.example", &[("rule", &locale.get(rule.key()))])
+        );
+        text.sections[1].value = locale.stimulus_word(&colors[word].1);
+        text.sections[1].style.color = colors[ink].2;
+        text.sections[2].value = format!(
+            "\n{}",
+            locale.format(
+                "instructions.answer",
+                &[("color", &locale.color_name(&colors[answer].1))]
+            )
+        );
+        text.sections[2].style.color = colors[answer].2.with_a(reveal.clamp(0., 1.));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn menu_navigation(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+    mut selection: ResMut<MenuSelection>,
+    mut settings: ResMut<GameSettings>,
+    mut game_mode: ResMut<GameMode>,
+    mut volumes: ResMut<AudioVolumes>,
+    state: Res<State<AppState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    instructions_shown: Res<InstructionsShown>,
+    missing_voice: Option<Res<MissingVoiceClip>>,
+    invalid_settings: Option<Res<InvalidSettings>>,
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut button_query: Query<(&MenuButton, &mut BackgroundColor)>,
+) {
+    let count = button_query.iter().count();
+    if count == 0 {
+        return;
+    }
+
+    let mut activated = None;
+    for (interaction, button) in interaction_query.iter() {
+        match interaction {
+            Interaction::Pressed => {
+                selection.0 = button.index;
+                activated = Some(button.action);
+            }
+            Interaction::Hovered => selection.0 = button.index,
+            Interaction::None => {}
+        }
+    }
+
+    // just_pressed, so the key that returned us here from another screen doesn't carry over.
+    if keyboard_input.any_just_pressed([KeyCode::Up, KeyCode::W]) {
+        selection.0 = (selection.0 + count - 1) % count;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Down, KeyCode::S]) {
+        selection.0 = (selection.0 + 1) % count;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Return]) {
+        activated = button_query
+            .iter()
+            .find(|(button, _)| button.index == selection.0)
+            .map(|(button, _)| button.action);
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        activated = Some(if *state.get() == AppState::Menu {
+            MenuAction::Quit
+        } else {
+            MenuAction::Back
+        });
+    }
+
+    // New players see the instructions first, after that Play is the same as Start Game.
+    if activated == Some(MenuAction::Play) && instructions_shown.0 {
+        activated = Some(MenuAction::StartGame);
+    }
+
+    // The reason is already on screen.
+    if (missing_voice.is_some() || invalid_settings.is_some())
+        && matches!(
+            activated,
+            Some(MenuAction::Practice) | Some(MenuAction::StartGame)
+        )
+    {
+        activated = None;
+    }
+
+    match activated {
+        Some(MenuAction::Play) => next_state.set(AppState::Instructions),
+        Some(MenuAction::CycleMode) => *game_mode = game_mode.next(),
+        Some(MenuAction::Settings) => next_state.set(AppState::Settings),
+        Some(MenuAction::Leaderboard) => next_state.set(AppState::Leaderboard),
+        Some(MenuAction::Instructions) => next_state.set(AppState::Instructions),
+        Some(MenuAction::Quit) => exit.send(AppExit),
+        Some(MenuAction::CycleResponseMode) => {
+            settings.response_mode = match settings.response_mode {
+                ResponseMode::Mouse => ResponseMode::Keyboard,
+                ResponseMode::Keyboard => ResponseMode::Mouse,
+            }
+        }
+        Some(MenuAction::CycleLives) => settings.lives = settings.lives % MAX_LIVES + 1,
+        Some(MenuAction::CycleRoundLength) => {
+            settings.round_seconds = ROUND_LENGTHS
+                .iter()
+                .position(|length| *length == settings.round_seconds)
+                .map_or(ROUND_LENGTHS[0], |index| {
+                    ROUND_LENGTHS[(index + 1) % ROUND_LENGTHS.len()]
+                })
+        }
+        Some(MenuAction::CycleMasterVolume) => volumes.master = next_volume(volumes.master),
+        Some(MenuAction::CycleMusicVolume) => volumes.music = next_volume(volumes.music),
+        Some(MenuAction::CycleSfxVolume) => volumes.sfx = next_volume(volumes.sfx),
+        Some(MenuAction::Practice) => {
+            commands.insert_resource(Practice::new(settings.practice_trials));
+            next_state.set(AppState::GameStart)
+        }
+        Some(MenuAction::StartGame) => {
+            commands.remove_resource::<Practice>();
+            next_state.set(AppState::GameStart)
+        }
+        Some(MenuAction::Back) => next_state.set(AppState::Menu),
+        None => {}
+    }
+
+    for (button, mut color) in button_query.iter_mut() {
+        *color = if button.index == selection.0 {
+            HOVERED_BUTTON_COLOR.into()
+        } else {
+            BUTTON_COLOR.into()
+        };
+    }
+}
+
+// The next of `VOLUME_LEVELS`, a level set by hand in between goes back to silence.
+fn next_volume(volume: f32) -> f32 {
+    VOLUME_LEVELS
+        .iter()
+        .position(|level| *level == volume)
+        .map_or(VOLUME_LEVELS[0], |index| {
+            VOLUME_LEVELS[(index + 1) % VOLUME_LEVELS.len()]
+        })
+}
+
+fn update_button_labels(
+    settings: Res<GameSettings>,
+    game_mode: Res<GameMode>,
+    volumes: Res<AudioVolumes>,
+    locale: Res<Locale>,
+    mut label_query: Query<(&ButtonLabel, &mut Text)>,
+) {
+    if !settings.is_changed() && !game_mode.is_changed() && !volumes.is_changed() {
+        return;
+    }
+    for (label_action, mut text) in label_query.iter_mut() {
+        text.sections[0].value = label(label_action.0, &settings, &game_mode, &volumes, &locale);
+    }
+}
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+enum PauseAction {
+    Resume,
+    Restart,
+    QuitToMenu,
+}
+
+pub struct PausePlugin;
+
+// The sounds that stop while paused, sound effects are short enough to finish.
+type PausedSounds<'w, 's> = Query<'w, 's, &'static AudioSink, Or<(With<Music>, With<SpokenWord>)>>;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Paused), pause_setup)
+            .add_systems(OnExit(AppState::Paused), pause_cleanup)
+            .add_systems(Update, pause_input.run_if(in_state(AppState::Paused)));
+    }
+}
+
+// Virtual time is stopped while paused, so the round timer, the target circles and any
+// reaction time being measured all pick up where they left off. The music and the spoken
+// color word wait too.
+fn pause_setup(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    locale: Res<Locale>,
+    fonts: Res<Fonts>,
+    sink_query: PausedSounds,
+) {
+    time.pause();
+    for sink in sink_query.iter() {
+        sink.pause();
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.75).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            StateScoped(&[AppState::Paused]),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                locale.get("pause.title"),
+                TextStyle {
+                    font: fonts.ui.clone(),
+                    font_size: 100.0,
+                    color: Color::WHITE,
+                },
+            ));
+
+            for (action, label) in [
+                (PauseAction::Resume, "pause.resume"),
+                (PauseAction::Restart, "pause.restart"),
+                (PauseAction::QuitToMenu, "pause.quit"),
+            ] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(400.0),
+                                height: Val::Px(65.0),
+                                margin: UiRect::all(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        action,
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            locale.get(label),
+                            TextStyle {
+                                font: fonts.ui.clone(),
+                                font_size: 40.0,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+fn pause_cleanup(mut time: ResMut<Time<Virtual>>, sink_query: PausedSounds) {
+    time.unpause();
+    for sink in sink_query.iter() {
+        sink.play();
+    }
+}
+
+fn pause_input(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut button_query: Query<
+        (&Interaction, &PauseAction, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+) {
+    let mut action = None;
+    for (interaction, button_action, mut color) in button_query.iter_mut() {
+        *color = match interaction {
+            Interaction::Pressed => {
+                action = Some(*button_action);
+                HOVERED_BUTTON_COLOR.into()
+            }
+            Interaction::Hovered => HOVERED_BUTTON_COLOR.into(),
+            Interaction::None => BUTTON_COLOR.into(),
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        action = Some(PauseAction::Resume);
+    } else if keyboard_input.just_pressed(KeyCode::R) {
+        action = Some(PauseAction::Restart);
+    } else if keyboard_input.just_pressed(KeyCode::Q) {
+        action = Some(PauseAction::QuitToMenu);
+    }
+
+    let Some(action) = action else {
+        return;
+    };
+
+    // Restarting or quitting leaves the round's states, which takes the targets and HUD with it.
+    next_state.set(match action {
+        PauseAction::Resume => AppState::InGame,
+        PauseAction::Restart => AppState::GameStart,
+        PauseAction::QuitToMenu => AppState::Menu,
+    });
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+// The single source of randomness for anything the player sees. A session started with the
+// same seed presents the same sequence of trials.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Uses the configured seed if there is one, otherwise picks a fresh one so it can still be
+    // recorded and replayed.
+    pub fn from_seed_or_entropy(seed: Option<u64>) -> Self {
+        GameRng::new(seed.unwrap_or_else(|| thread_rng().gen()))
+    }
+}
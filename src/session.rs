@@ -0,0 +1,291 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use chrono::Local;
+use serde::Serialize;
+
+use crate::{cli_arg, CategorySummary, StaircaseSummary, Stimulus};
+
+pub const RESULTS_DIR: &str = "results";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub enum TrialOutcome {
+    Unanswered,
+    Correct,
+    Wrong,
+    Missed,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub enum Congruency {
+    Congruent,
+    Incongruent,
+    // The word doesn't name a color at all, as in the emotional Stroop task.
+    Neutral,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Trial {
+    pub word: String,
+    // The word list an emotional trial's word came from.
+    pub category: Option<String>,
+    pub ink: String,
+    pub rule: String,
+    pub congruency: Congruency,
+    pub target_speed: f32,
+    pub stimulus_onset: f64,
+    pub response_time: Option<f64>,
+    pub reaction_time_ms: Option<f64>,
+    pub outcome: TrialOutcome,
+}
+
+impl Trial {
+    pub fn new(
+        word: String,
+        ink: String,
+        rule: String,
+        congruency: Congruency,
+        target_speed: f32,
+        stimulus_onset: f64,
+    ) -> Self {
+        Trial {
+            word,
+            category: None,
+            ink,
+            rule,
+            congruency,
+            target_speed,
+            stimulus_onset,
+            response_time: None,
+            reaction_time_ms: None,
+            outcome: TrialOutcome::Unanswered,
+        }
+    }
+
+    // Only the first response to a trial counts, later clicks or misses are ignored.
+    pub fn resolve(&mut self, outcome: TrialOutcome, now: f64) {
+        if self.outcome != TrialOutcome::Unanswered {
+            return;
+        }
+        self.outcome = outcome;
+        if outcome != TrialOutcome::Missed {
+            self.response_time = Some(now);
+            self.reaction_time_ms = Some((now - self.stimulus_onset) * 1000.);
+        }
+    }
+}
+
+// Reset at the start of every session but deliberately left alone on GameOver so the
+// finished session can still be read back out.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TrialLog {
+    pub trials: Vec<Trial>,
+}
+
+impl TrialLog {
+    pub fn current_mut(&mut self) -> Option<&mut Trial> {
+        self.trials.last_mut()
+    }
+
+    // Mean reaction time over correctly answered trials of the given congruency.
+    pub fn mean_reaction_time_ms(&self, congruency: Congruency) -> Option<f64> {
+        self.mean_correct_reaction_time_ms(|trial| trial.congruency == congruency)
+    }
+
+    // Mean reaction time over correctly answered trials from the given word list.
+    pub fn mean_category_reaction_time_ms(&self, category: &str) -> Option<f64> {
+        self.mean_correct_reaction_time_ms(|trial| trial.category.as_deref() == Some(category))
+    }
+
+    fn mean_correct_reaction_time_ms(&self, filter: impl Fn(&Trial) -> bool) -> Option<f64> {
+        let times: Vec<f64> = self
+            .trials
+            .iter()
+            .filter(|trial| filter(trial) && trial.outcome == TrialOutcome::Correct)
+            .filter_map(|trial| trial.reaction_time_ms)
+            .collect();
+        if times.is_empty() {
+            None
+        } else {
+            Some(times.iter().sum::<f64>() / times.len() as f64)
+        }
+    }
+
+    // Incongruent minus congruent mean reaction time, if both kinds were answered.
+    pub fn stroop_effect_ms(&self) -> Option<f64> {
+        Some(
+            self.mean_reaction_time_ms(Congruency::Incongruent)?
+                - self.mean_reaction_time_ms(Congruency::Congruent)?,
+        )
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ParticipantId(pub String);
+
+impl ParticipantId {
+    // Taken from `--participant <id>` on the command line.
+    pub fn from_args() -> Self {
+        ParticipantId(cli_arg("--participant").unwrap_or_else(|| "anonymous".to_string()))
+    }
+}
+
+#[derive(Resource, Clone, Debug, Serialize)]
+pub struct SessionResults {
+    pub participant_id: String,
+    pub timestamp: String,
+    pub mode: String,
+    // For auditory sessions each trial's `ink` is the color its voice stands for. For counting
+    // sessions `word` is what was shown, e.g. "3 3", and `ink` how many items it has.
+    pub stimulus: Stimulus,
+    // Language the stimulus words were shown in, each trial's `word` is the palette name.
+    pub stimulus_language: String,
+    pub seed: u64,
+    pub score: usize,
+    pub timer_expired: bool,
+    pub mean_congruent_rt_ms: Option<f64>,
+    pub mean_incongruent_rt_ms: Option<f64>,
+    pub stroop_effect_ms: Option<f64>,
+    // Only present for training sessions.
+    pub staircase: Option<StaircaseSummary>,
+    // Only filled in for emotional sessions, one per word list.
+    pub categories: Vec<CategorySummary>,
+    pub trials: Vec<Trial>,
+}
+
+// One CSV row per trial, with the session-level fields repeated on every row.
+#[derive(Serialize)]
+struct TrialRow<'a> {
+    participant_id: &'a str,
+    timestamp: &'a str,
+    mode: &'a str,
+    stimulus: Stimulus,
+    stimulus_language: &'a str,
+    seed: u64,
+    score: usize,
+    timer_expired: bool,
+    stroop_effect_ms: Option<f64>,
+    threshold_speed: Option<f32>,
+    trial: usize,
+    word: &'a str,
+    category: Option<&'a str>,
+    // The category's bias, repeated on each of its trials.
+    category_bias_ms: Option<f64>,
+    ink: &'a str,
+    rule: &'a str,
+    congruency: Congruency,
+    target_speed: f32,
+    stimulus_onset: f64,
+    response_time: Option<f64>,
+    reaction_time_ms: Option<f64>,
+    outcome: TrialOutcome,
+}
+
+impl SessionResults {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        participant_id: &ParticipantId,
+        mode: String,
+        stimulus: Stimulus,
+        stimulus_language: String,
+        seed: u64,
+        score: usize,
+        timer_expired: bool,
+        log: &TrialLog,
+        staircase: Option<StaircaseSummary>,
+        categories: Vec<CategorySummary>,
+    ) -> Self {
+        SessionResults {
+            participant_id: participant_id.0.clone(),
+            timestamp: Local::now().to_rfc3339(),
+            mode,
+            stimulus,
+            stimulus_language,
+            seed,
+            score,
+            timer_expired,
+            mean_congruent_rt_ms: log.mean_reaction_time_ms(Congruency::Congruent),
+            mean_incongruent_rt_ms: log.mean_reaction_time_ms(Congruency::Incongruent),
+            stroop_effect_ms: log.stroop_effect_ms(),
+            staircase,
+            categories,
+            trials: log.trials.clone(),
+        }
+    }
+
+    fn file_stem(&self) -> String {
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|time| time.format("%Y%m%d_%H%M%S").to_string())
+            .unwrap_or_else(|_| self.timestamp.replace(':', "-"));
+        // The id comes straight off the command line, so anything that could leave the results
+        // folder, like `/` or `..`, is replaced before it becomes part of a path.
+        let participant: String = self
+            .participant_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}_{}", participant, timestamp)
+    }
+
+    pub fn write_csv(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for (index, trial) in self.trials.iter().enumerate() {
+            writer.serialize(TrialRow {
+                participant_id: &self.participant_id,
+                timestamp: &self.timestamp,
+                mode: &self.mode,
+                stimulus: self.stimulus,
+                stimulus_language: &self.stimulus_language,
+                seed: self.seed,
+                score: self.score,
+                timer_expired: self.timer_expired,
+                stroop_effect_ms: self.stroop_effect_ms,
+                threshold_speed: self
+                    .staircase
+                    .as_ref()
+                    .and_then(|staircase| staircase.threshold_speed),
+                trial: index + 1,
+                word: &trial.word,
+                category: trial.category.as_deref(),
+                category_bias_ms: self
+                    .categories
+                    .iter()
+                    .find(|summary| Some(summary.category.as_str()) == trial.category.as_deref())
+                    .and_then(|summary| summary.bias_ms),
+                ink: &trial.ink,
+                rule: &trial.rule,
+                congruency: trial.congruency,
+                target_speed: trial.target_speed,
+                stimulus_onset: trial.stimulus_onset,
+                response_time: trial.response_time,
+                reaction_time_ms: trial.reaction_time_ms,
+                outcome: trial.outcome,
+            })?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // Writes `<participant>_<timestamp>.csv` and `.json` into `dir`, returning both paths.
+    pub fn export(&self, dir: &Path) -> Result<(PathBuf, PathBuf), Box<dyn std::error::Error>> {
+        fs::create_dir_all(dir)?;
+        let stem = self.file_stem();
+        let csv_path = dir.join(format!("{}.csv", stem));
+        let json_path = dir.join(format!("{}.json", stem));
+        self.write_csv(&csv_path)?;
+        self.write_json(&json_path)?;
+        Ok((csv_path, json_path))
+    }
+}
@@ -0,0 +1,278 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+pub const SETTINGS_PATH: &str = "settings.ron";
+// A correct and an incorrect circle need a color each.
+pub const MIN_PALETTE_SIZE: usize = 2;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaletteColor {
+    pub name: String,
+    pub rgb: (f32, f32, f32),
+    // Letter that answers with this color in keyboard mode, instead of the first letter of
+    // its name.
+    #[serde(default)]
+    pub key: Option<char>,
+}
+
+impl PaletteColor {
+    pub fn color(&self) -> Color {
+        Color::rgb(self.rgb.0, self.rgb.1, self.rgb.2)
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ResponseMode {
+    // Click on the correct moving circle.
+    #[default]
+    Mouse,
+    // Press the key bound to the correct color, wherever the circles are.
+    Keyboard,
+}
+
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    pub round_seconds: f32,
+    // Wrong answers and misses each cost a life, the game ends when they run out.
+    pub lives: usize,
+    // Length of the untimed practice block offered after the instructions.
+    pub practice_trials: usize,
+    pub target_speed: f32,
+    pub hit_radius: f32,
+    pub circle_radius: f32,
+    pub number_entities: usize,
+    pub response_mode: ResponseMode,
+    // Printed or spoken color words, emotional words or repeated digits to count.
+    // `--stimulus <visual|auditory|emotional|counting>` on the command line wins.
+    pub stimulus: Stimulus,
+    // Auditory stimuli only, one voice for each palette color, in the same order.
+    pub voices: Vec<Voice>,
+    // Emotional stimuli only, the words shown and the category each belongs to.
+    pub emotional_words: EmotionalWords,
+    // Counting stimuli only.
+    pub counting: CountingSettings,
+    // Where finished sessions are exported to.
+    pub results_dir: PathBuf,
+    // Where the leaderboard is kept, the user's data directory when None.
+    pub data_dir: Option<PathBuf>,
+    // Fixes the random sequence of every session, `--seed <n>` on the command line wins.
+    pub seed: Option<u64>,
+    // How many scores the local leaderboard keeps.
+    pub leaderboard_size: usize,
+    pub difficulty: DifficultySettings,
+    // Only used in training mode.
+    pub staircase: StaircaseSettings,
+    // The target colors, at least two of them.
+    pub palette: Vec<PaletteColor>,
+    // Replaces `palette` with a built-in one that's safe for this color vision deficiency.
+    pub cvd_palette: Option<ColorVision>,
+    // Draws the target colors as seen with this deficiency, F9 cycles through them in game.
+    pub cvd_simulation: Option<ColorVision>,
+    // Language of the menus and instructions, `--language <code>` on the command line wins.
+    pub language: String,
+    // Language of the color words shown as stimuli, the UI language when left out.
+    // `--stimulus-language <code>` on the command line wins.
+    pub stimulus_language: Option<String>,
+    // Where `<language>.ron` string tables are read from.
+    pub locales_dir: PathBuf,
+    pub fonts: FontSettings,
+    pub feedback: FeedbackSettings,
+    pub music: MusicSettings,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        let names = ["RED", "YELLOW", "GREEN", "BLUE", "PURPLE"];
+        GameSettings {
+            round_seconds: 60.,
+            lives: 3,
+            practice_trials: 5,
+            target_speed: 200.,
+            hit_radius: CIRCLE_RADIUS + 1.,
+            circle_radius: CIRCLE_RADIUS,
+            number_entities: NUMBER_ENTITIES,
+            response_mode: ResponseMode::Mouse,
+            stimulus: Stimulus::Visual,
+            voices: default_voices(),
+            emotional_words: EmotionalWords::default(),
+            counting: CountingSettings::default(),
+            results_dir: PathBuf::from(RESULTS_DIR),
+            data_dir: None,
+            seed: None,
+            leaderboard_size: 10,
+            difficulty: DifficultySettings::default(),
+            staircase: StaircaseSettings::default(),
+            palette: names
+                .iter()
+                .zip(COLOR_SELECTION)
+                .map(|(name, color)| {
+                    let [r, g, b, _] = color.as_rgba_f32();
+                    PaletteColor {
+                        name: name.to_string(),
+                        rgb: (r, g, b),
+                        key: None,
+                    }
+                })
+                .collect(),
+            cvd_palette: None,
+            cvd_simulation: None,
+            language: DEFAULT_LANGUAGE.to_string(),
+            stimulus_language: None,
+            locales_dir: PathBuf::from(LOCALES_DIR),
+            fonts: FontSettings::default(),
+            feedback: FeedbackSettings::default(),
+            music: MusicSettings::default(),
+        }
+    }
+}
+
+impl GameSettings {
+    pub fn locale(&self) -> Result<Locale, String> {
+        Locale::new(
+            &self.locales_dir,
+            &self.language,
+            self.stimulus_language.as_deref().unwrap_or(&self.language),
+        )
+    }
+
+    // `data_dir`, or else `<data dir>/stroop`, e.g. `~/.local/share/stroop` on Linux.
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir.clone().unwrap_or_else(|| {
+            dirs::data_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("stroop")
+        })
+    }
+
+    // Reads `--settings <path>` or `settings.ron`, falling back to the built-in defaults only
+    // when the file is missing. Any field left out of the file keeps its default value.
+    pub fn load() -> Result<Self, String> {
+        let path = cli_arg("--settings").unwrap_or_else(|| SETTINGS_PATH.to_string());
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                let settings: GameSettings = ron::from_str(&contents)
+                    .map_err(|err| format!("Invalid settings in {}: {}", path, err))?;
+                if settings.palette.len() < MIN_PALETTE_SIZE {
+                    return Err(format!(
+                        "The palette in {} needs at least {} colors",
+                        path, MIN_PALETTE_SIZE
+                    ));
+                }
+                if let Some(key) = settings
+                    .palette
+                    .iter()
+                    .filter_map(|color| color.key)
+                    .find(|key| letter_key(*key).is_none())
+                {
+                    return Err(format!("{:?} in {} isn't a letter key", key, path));
+                }
+                let keys: Vec<char> = settings
+                    .palette
+                    .iter()
+                    .filter_map(|color| color.key.map(|key| key.to_ascii_uppercase()))
+                    .collect();
+                if let Some((_, key)) = keys
+                    .iter()
+                    .enumerate()
+                    .find(|(index, key)| keys[..*index].contains(key))
+                {
+                    return Err(format!(
+                        "{:?} is used for more than one color in {}",
+                        key, path
+                    ));
+                }
+                Ok(settings)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(GameSettings::default()),
+            Err(err) => Err(format!("Could not read {}: {}", path, err)),
+        }
+        .map(|mut settings| {
+            if let Some(vision) = settings.cvd_palette {
+                settings.palette = vision.palette();
+            }
+            if let Some(seed) = cli_arg("--seed").and_then(|seed| seed.parse().ok()) {
+                settings.seed = Some(seed);
+            }
+            if let Some(language) = cli_arg("--language") {
+                settings.language = language;
+            }
+            if let Some(language) = cli_arg("--stimulus-language") {
+                settings.stimulus_language = Some(language);
+            }
+            if let Some(stimulus) = cli_arg("--stimulus").and_then(|arg| Stimulus::from_arg(&arg)) {
+                settings.stimulus = stimulus;
+            }
+            settings
+        })
+        .and_then(|settings| settings.validate().map(|_| settings))
+    }
+
+    // Catches values that would otherwise panic or stall the game once it's running.
+    pub fn validate(&self) -> Result<(), String> {
+        // A timer can't count down NaN or negative seconds, and a circle needs a size.
+        for (name, value) in [
+            ("round_seconds", self.round_seconds),
+            ("hit_radius", self.hit_radius),
+            ("circle_radius", self.circle_radius),
+        ] {
+            if !(value.is_finite() && value > 0.) {
+                return Err(format!("{} needs to be above 0, not {}", name, value));
+            }
+        }
+        // Zero is allowed, it holds the targets still.
+        if !(self.target_speed.is_finite() && self.target_speed >= 0.) {
+            return Err(format!(
+                "target_speed needs to be 0 or above, not {}",
+                self.target_speed
+            ));
+        }
+        self.difficulty.validate()?;
+        self.staircase.validate()?;
+        if let DifficultyCurve::Adaptive { .. } = self.difficulty.training {
+            return Err(
+                "The training difficulty curve can't be Adaptive, its staircase already adapts \
+                 to the player"
+                    .to_string(),
+            );
+        }
+        if self.stimulus == Stimulus::Auditory && self.voices.len() < self.palette.len() {
+            return Err(format!(
+                "Auditory stimuli need a voice for each of the {} palette colors",
+                self.palette.len()
+            ));
+        }
+        if self.stimulus == Stimulus::Emotional {
+            self.emotional_words.validate()?;
+        }
+        if self.stimulus == Stimulus::Counting {
+            self.counting.validate()?;
+        }
+        Ok(())
+    }
+}
+
+// Why the settings file couldn't be used. The defaults are only there to show the error, the
+// menus refuse to start a game rather than run a protocol with the wrong parameters.
+#[derive(Resource, Clone, Debug)]
+pub struct InvalidSettings(pub String);
+
+pub fn load_settings(mut commands: Commands) {
+    let settings = GameSettings::load().unwrap_or_else(|err| {
+        error!("{}", err);
+        commands.insert_resource(InvalidSettings(err));
+        GameSettings::default()
+    });
+    let locale = settings.locale().unwrap_or_else(|err| {
+        warn!("{}, using English", err);
+        Locale::default()
+    });
+    commands.insert_resource(GameRng::from_seed_or_entropy(settings.seed));
+    commands.insert_resource(locale);
+    commands.insert_resource(settings);
+}
@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaircaseSettings {
+    // Consecutive correct answers before the targets speed up (the "2" in 2-down/1-up).
+    pub down: usize,
+    // Consecutive errors before they slow down again.
+    pub up: usize,
+    pub start_speed: f32,
+    pub step: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    // The threshold is the mean speed over this many of the most recent reversals.
+    pub threshold_reversals: usize,
+}
+
+impl Default for StaircaseSettings {
+    fn default() -> Self {
+        StaircaseSettings {
+            down: 2,
+            up: 1,
+            start_speed: 200.,
+            step: 20.,
+            min_speed: 50.,
+            max_speed: 800.,
+            threshold_reversals: 6,
+        }
+    }
+}
+
+impl StaircaseSettings {
+    // Returns why these can't be used, if they can't. The speed is clamped between the bounds
+    // after every step, which panics if they're the wrong way round.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_speed.is_finite()
+            && self.max_speed.is_finite()
+            && 0. <= self.min_speed
+            && self.min_speed <= self.max_speed)
+        {
+            return Err(format!(
+                "staircase speeds need 0 <= min_speed <= max_speed, not {} and {}",
+                self.min_speed, self.max_speed
+            ));
+        }
+        if !(self.min_speed..=self.max_speed).contains(&self.start_speed) {
+            return Err(format!(
+                "staircase.start_speed needs to be between min_speed and max_speed, not {}",
+                self.start_speed
+            ));
+        }
+        if !(self.step.is_finite() && self.step > 0.) {
+            return Err(format!(
+                "staircase.step needs to be above 0, not {}",
+                self.step
+            ));
+        }
+        if self.down < 1 || self.up < 1 {
+            return Err(format!(
+                "staircase.down and staircase.up need to be at least 1, not {} and {}",
+                self.down, self.up
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub enum StaircaseDirection {
+    Harder,
+    Easier,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StaircaseStep {
+    pub trial: usize,
+    pub speed: f32,
+    pub correct: bool,
+    pub reversal: bool,
+}
+
+// Tracks target speed over a training session, converging on the speed the player gets right
+// about 70% of the time with the default 2-down/1-up rule.
+#[derive(Resource, Clone, Debug, Serialize)]
+pub struct Staircase {
+    pub speed: f32,
+    pub track: Vec<StaircaseStep>,
+    pub reversals: Vec<f32>,
+    #[serde(skip)]
+    correct_run: usize,
+    #[serde(skip)]
+    wrong_run: usize,
+    #[serde(skip)]
+    last_direction: Option<StaircaseDirection>,
+}
+
+impl Staircase {
+    pub fn new(settings: &StaircaseSettings) -> Self {
+        Staircase {
+            speed: settings.start_speed,
+            track: Vec::new(),
+            reversals: Vec::new(),
+            correct_run: 0,
+            wrong_run: 0,
+            last_direction: None,
+        }
+    }
+
+    pub fn record(&mut self, correct: bool, settings: &StaircaseSettings) {
+        let direction = if correct {
+            self.correct_run += 1;
+            self.wrong_run = 0;
+            (self.correct_run >= settings.down).then_some(StaircaseDirection::Harder)
+        } else {
+            self.wrong_run += 1;
+            self.correct_run = 0;
+            (self.wrong_run >= settings.up).then_some(StaircaseDirection::Easier)
+        };
+
+        let reversal = direction.is_some()
+            && self.last_direction.is_some()
+            && direction != self.last_direction;
+        self.track.push(StaircaseStep {
+            trial: self.track.len() + 1,
+            speed: self.speed,
+            correct,
+            reversal,
+        });
+        if reversal {
+            self.reversals.push(self.speed);
+        }
+
+        if let Some(direction) = direction {
+            self.correct_run = 0;
+            self.wrong_run = 0;
+            self.last_direction = Some(direction);
+            let step = match direction {
+                StaircaseDirection::Harder => settings.step,
+                StaircaseDirection::Easier => -settings.step,
+            };
+            self.speed = (self.speed + step).clamp(settings.min_speed, settings.max_speed);
+        }
+    }
+
+    pub fn threshold(&self, settings: &StaircaseSettings) -> Option<f32> {
+        let count = settings.threshold_reversals.min(self.reversals.len());
+        if count == 0 {
+            return None;
+        }
+        let recent = &self.reversals[self.reversals.len() - count..];
+        Some(recent.iter().sum::<f32>() / count as f32)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StaircaseSummary {
+    pub threshold_speed: Option<f32>,
+    pub reversals: Vec<f32>,
+    pub track: Vec<StaircaseStep>,
+}
+
+impl StaircaseSummary {
+    pub fn new(staircase: &Staircase, settings: &StaircaseSettings) -> Self {
+        StaircaseSummary {
+            threshold_speed: staircase.threshold(settings),
+            reversals: staircase.reversals.clone(),
+            track: staircase.track.clone(),
+        }
+    }
+}
+
+// Feeds the outcome of the trial that just finished into the staircase.
+pub fn update_staircase(
+    mut staircase: ResMut<Staircase>,
+    game_mode: Res<GameMode>,
+    settings: Res<GameSettings>,
+    trial_log: Res<TrialLog>,
+) {
+    if *game_mode != GameMode::Training {
+        return;
+    }
+
+    if let Some(trial) = trial_log.trials.last() {
+        match trial.outcome {
+            TrialOutcome::Correct => staircase.record(true, &settings.staircase),
+            TrialOutcome::Wrong | TrialOutcome::Missed => {
+                staircase.record(false, &settings.staircase)
+            }
+            TrialOutcome::Unanswered => {}
+        }
+    }
+}
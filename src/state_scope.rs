@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+// Everything that makes up a round in progress, kept across pauses and between rounds. The
+// game restarts by going back to `GameStart`, which isn't included, so a restart starts clean.
+pub const ROUND_STATES: &[AppState] = &[AppState::NextRound, AppState::InGame, AppState::Paused];
+
+// The target circles only live until the round is answered, missed or abandoned.
+pub const TARGET_STATES: &[AppState] = &[AppState::InGame, AppState::Paused];
+
+// Ties an entity to the states it belongs to. It's despawned, along with its children, as soon
+// as the game moves to a state that isn't one of them.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct StateScoped(pub &'static [AppState]);
+
+pub struct StateScopePlugin;
+
+impl Plugin for StateScopePlugin {
+    fn build(&self, app: &mut App) {
+        // Runs ahead of every transition, whichever states it's between, so states added later
+        // are covered without having to list them here.
+        app.add_systems(
+            StateTransition,
+            (despawn_out_of_scope, apply_deferred)
+                .chain()
+                .before(apply_state_transition::<AppState>),
+        );
+    }
+}
+
+// Judged against the state about to be entered, so anything scoped to it survives the
+// transition, and the new state's `OnEnter` systems only see what's still in scope.
+fn despawn_out_of_scope(
+    mut commands: Commands,
+    state: Res<State<AppState>>,
+    next_state: Res<NextState<AppState>>,
+    scoped_query: Query<(Entity, &StateScoped)>,
+) {
+    let Some(entering) = next_state.0.filter(|next| next != state.get()) else {
+        return;
+    };
+    for (entity, scope) in scoped_query.iter() {
+        if !scope.0.contains(&entering) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Stimulus {
+    // The color word is printed in colored ink.
+    #[default]
+    Visual,
+    // The color word is spoken, and the voice it's spoken in stands for a color the way the ink
+    // does for a printed word.
+    Auditory,
+    // Neutral and emotional words from `GameSettings::emotional_words` are printed in colored
+    // ink, and the answer is always the ink.
+    Emotional,
+    // A digit or `CountingSettings::glyph` is repeated, and the answer is how many times.
+    Counting,
+}
+
+impl Stimulus {
+    // `--stimulus <visual|auditory|emotional|counting>`.
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "visual" => Some(Stimulus::Visual),
+            "auditory" => Some(Stimulus::Auditory),
+            "emotional" => Some(Stimulus::Emotional),
+            "counting" => Some(Stimulus::Counting),
+            _ => None,
+        }
+    }
+
+    // How many palette colors the targets are drawn from, every count needs a number key of
+    // its own.
+    pub fn max_colors(&self) -> usize {
+        match self {
+            Stimulus::Counting => MAX_COUNT,
+            _ => usize::MAX,
+        }
+    }
+}
+
+// A target circle's palette entry, as listed in `ColorResource`.
+pub type Target = (Handle<ColorMaterial>, String, Color);
+
+// One round's stimulus, made by the generator for the session's `Stimulus` from the colors of
+// the correct and the incorrect circle.
+pub struct StimulusTrial {
+    // Recorded as the trial's word and ink.
+    pub word: String,
+    pub ink: String,
+    // Printed above the targets in `color`, empty when the word is spoken instead.
+    pub text: String,
+    pub color: Color,
+    // The `Locale` key of the rule shown to the player, and the rule as recorded.
+    pub rule_key: &'static str,
+    pub rule: &'static str,
+    pub congruency: Congruency,
+    pub category: Option<String>,
+    pub spoken: Option<SpokenWord>,
+}
+
+// The classic stimulus, a color word printed in colored ink or spoken in a voice standing for a
+// color. The rule decides which half names the correct color. The other half names the
+// incorrect circle's color, unless the trial is congruent and both halves agree.
+pub fn color_word_trial(
+    settings: &GameSettings,
+    congruent_ratio: f64,
+    locale: &Locale,
+    voice_clips: Option<&VoiceClips>,
+    (correct, wrong): (&Target, &Target),
+    rng: &mut impl Rng,
+) -> StimulusTrial {
+    let rule = *WORD_OR_COLOR.choose(rng).unwrap();
+    let congruency = if rng.gen_bool(congruent_ratio.clamp(0., 1.)) {
+        Congruency::Congruent
+    } else {
+        Congruency::Incongruent
+    };
+    let other = match congruency {
+        Congruency::Congruent => correct,
+        Congruency::Incongruent | Congruency::Neutral => wrong,
+    };
+    let (word, ink) = match rule {
+        WordOrColor::Word => (correct, other),
+        WordOrColor::Color => (other, correct),
+    };
+
+    let trial = StimulusTrial {
+        word: word.1.clone(),
+        ink: ink.1.clone(),
+        text: locale.stimulus_word(&word.1),
+        color: ink.2,
+        rule_key: rule.key(),
+        rule: rule.label(),
+        congruency,
+        category: None,
+        spoken: None,
+    };
+    if settings.stimulus != Stimulus::Auditory {
+        return trial;
+    }
+    // Nothing is printed, the voice takes the place of the ink.
+    StimulusTrial {
+        text: String::new(),
+        rule_key: rule.spoken_key(),
+        spoken: spoken_word(settings, voice_clips, &word.1, &ink.1),
+        ..trial
+    }
+}
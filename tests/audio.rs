@@ -0,0 +1,86 @@
+mod common;
+
+use std::fs;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+fn music(game: &mut HeadlessGame) -> Vec<Handle<AudioSource>> {
+    game.app
+        .world
+        .query_filtered::<&Handle<AudioSource>, With<Music>>()
+        .iter(&game.app.world)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn last_seconds_tick_higher_until_game_over() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        round_seconds: 3.,
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_audio();
+    game.start_game();
+
+    game.advance(Duration::from_millis(3100));
+
+    let audio = game.app.world.resource::<AudioAssets>().clone();
+    let sounds = game.sound_effects();
+    let ticks: Vec<usize> = sounds
+        .iter()
+        .filter_map(|sound| audio.ticks.iter().position(|tick| tick == sound))
+        .collect();
+    assert_eq!(
+        ticks,
+        [
+            COUNTDOWN_TICKS - 3,
+            COUNTDOWN_TICKS - 2,
+            COUNTDOWN_TICKS - 1
+        ]
+    );
+    assert_eq!(game.state(), AppState::GameOver);
+    assert!(sounds.contains(&audio.game_over));
+}
+
+#[test]
+fn music_changes_between_menu_and_game() {
+    let mut game = HeadlessGame::new().with_audio();
+    let (menu_music, game_music) = (Handle::weak_from_u128(100), Handle::weak_from_u128(101));
+    let mut audio = game.app.world.resource_mut::<AudioAssets>();
+    audio.menu_music = Some(menu_music.clone());
+    audio.game_music = Some(game_music.clone());
+
+    game.update();
+    assert_eq!(music(&mut game), [menu_music]);
+
+    game.start_game();
+    assert_eq!(music(&mut game), vec![game_music.clone()]);
+
+    // Pausing holds the same track rather than switching it.
+    game.tap(KeyCode::Escape);
+    assert_eq!(music(&mut game), [game_music]);
+}
+
+#[test]
+fn volumes_are_kept_in_the_data_dir() {
+    let data_dir = std::env::temp_dir().join("stroop-tests").join("volumes");
+    let _ = fs::remove_dir_all(&data_dir);
+    let settings = GameSettings {
+        data_dir: Some(data_dir.clone()),
+        ..Default::default()
+    };
+
+    let mut game = HeadlessGame::with_settings(settings.clone()).with_audio();
+    game.update();
+    game.app.world.resource_mut::<AudioVolumes>().master = 0.3;
+    game.update();
+    assert!(AudioVolumes::path(&data_dir).is_file());
+
+    let mut game = HeadlessGame::with_settings(settings).with_audio();
+    game.update();
+    assert_eq!(game.app.world.resource::<AudioVolumes>().master, 0.3);
+}
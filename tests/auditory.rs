@@ -0,0 +1,114 @@
+mod common;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+fn spoken_words(game: &mut HeadlessGame) -> Vec<SpokenWord> {
+    game.app
+        .world
+        .query::<&SpokenWord>()
+        .iter(&game.app.world)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn word_is_spoken_in_the_voice_of_its_ink() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Auditory,
+        seed: Some(3),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_voices();
+    game.start_game();
+
+    for _ in 0..10 {
+        let trial = game.last_trial();
+        let settings = game.app.world.resource::<GameSettings>().clone();
+        let index = |name: &str| {
+            settings
+                .palette
+                .iter()
+                .position(|color| color.name == name)
+                .unwrap()
+        };
+        let (word, voice) = (index(&trial.word), index(&trial.ink));
+
+        let spoken = spoken_words(&mut game);
+        assert_eq!(spoken.len(), 1);
+        assert_eq!(
+            spoken[0].clip,
+            Handle::weak_from_u128((100 * (voice + 1) + word) as u128)
+        );
+        assert_eq!(spoken[0].speed, settings.voices[voice].speed);
+
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+
+    assert_eq!(game.game_state().score(), 10);
+}
+
+#[test]
+fn spoken_word_is_cut_off_when_the_round_ends() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Auditory,
+        seed: Some(3),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_voices();
+    game.start_game();
+
+    game.tap(KeyCode::Escape);
+    game.tap(KeyCode::Q);
+
+    assert_eq!(game.state(), AppState::Menu);
+    assert!(spoken_words(&mut game).is_empty());
+}
+
+#[test]
+fn visual_sessions_speak_nothing() {
+    let mut game = HeadlessGame::new();
+    game.app.add_plugins(AuditoryPlugin);
+    game.start_game();
+
+    assert!(spoken_words(&mut game).is_empty());
+}
+
+#[test]
+fn missing_recordings_are_shown_and_refuse_to_start() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Auditory,
+        seed: Some(3),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_voices()
+    .with_menu();
+    game.update();
+    game.app
+        .world
+        .insert_resource(MissingVoiceClip("voices/RED.ogg".to_string()));
+    game.update();
+
+    let shown = game
+        .app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .any(|text| text.sections[0].value.contains("voices/RED.ogg"));
+    assert!(shown);
+
+    // Play, then both ways of starting from the instructions.
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    game.tap(KeyCode::Return);
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    game.update();
+    assert_eq!(game.state(), AppState::Instructions);
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use stroop::*;
+
+mod common;
+use common::*;
+
+fn colors(palette: &[PaletteColor]) -> Vec<Color> {
+    palette.iter().map(PaletteColor::color).collect()
+}
+
+#[test]
+fn built_in_palettes_stay_distinguishable() {
+    for vision in [
+        ColorVision::Protanopia,
+        ColorVision::Deuteranopia,
+        ColorVision::Tritanopia,
+    ] {
+        let palette = colors(&vision.palette());
+        for seen_with in [None, Some(vision)] {
+            let (_, _, difference) = closest_pair(&palette, seen_with).unwrap();
+            assert!(
+                difference >= MIN_COLOR_DIFFERENCE,
+                "{:?} palette seen with {:?}: delta E {}",
+                vision,
+                seen_with,
+                difference
+            );
+        }
+    }
+}
+
+#[test]
+fn default_palette_confuses_red_green_deficiencies() {
+    let settings = GameSettings::default();
+    let palette = colors(&settings.palette);
+
+    let (first, second, difference) =
+        closest_pair(&palette, Some(ColorVision::Protanopia)).unwrap();
+
+    assert!(difference < MIN_COLOR_DIFFERENCE);
+    let names = [
+        &settings.palette[first].name,
+        &settings.palette[second].name,
+    ];
+    assert!(names.contains(&&"GREEN".to_string()));
+}
+
+#[test]
+fn simulation_leaves_greys_alone() {
+    for vision in [
+        ColorVision::Protanopia,
+        ColorVision::Deuteranopia,
+        ColorVision::Tritanopia,
+    ] {
+        let grey = Color::rgb(0.5, 0.5, 0.5);
+        assert!(color_difference(grey, vision.simulate(grey)) < 1.);
+    }
+}
+
+fn text_colors(game: &mut HeadlessGame) -> Vec<Color> {
+    game.app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .flat_map(|text| text.sections.iter().map(|section| section.style.color))
+        .collect()
+}
+
+#[test]
+fn simulation_redraws_text_straight_away_and_back() {
+    let mut game = HeadlessGame::new();
+    game.app
+        .init_resource::<Assets<ColorMaterial>>()
+        .add_plugins(ColorVisionPlugin);
+    game.start_game();
+    let before = text_colors(&mut game);
+
+    game.app.world.resource_mut::<CvdSimulation>().0 = Some(ColorVision::Protanopia);
+    game.update();
+    let simulated = text_colors(&mut game);
+    assert_eq!(simulated.len(), before.len());
+    for (before, simulated) in before.iter().zip(&simulated) {
+        let expected = ColorVision::Protanopia.simulate(*before);
+        assert!(color_difference(expected, *simulated) < 0.1);
+    }
+
+    game.app.world.resource_mut::<CvdSimulation>().0 = None;
+    game.update();
+    for (before, after) in before.iter().zip(&text_colors(&mut game)) {
+        assert!(color_difference(*before, *after) < 0.1);
+    }
+}
@@ -0,0 +1,251 @@
+// Shared by several test binaries, none of which uses every helper.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::sprite::Mesh2dHandle;
+use bevy::time::TimeUpdateStrategy;
+use stroop::*;
+
+// Every update advances time by exactly one frame, regardless of wall-clock time.
+pub const FRAME: Duration = Duration::from_micros(16_667);
+
+const WINDOW_WIDTH: f32 = 800.;
+const WINDOW_HEIGHT: f32 = 600.;
+
+#[derive(Resource, Default)]
+pub struct MissedCircles(pub usize);
+
+fn count_missed_circles(
+    mut missed_circles: ResMut<MissedCircles>,
+    mut missed_event: EventReader<MissedCircleEvent>,
+) {
+    missed_circles.0 += missed_event.read().count();
+}
+
+// A windowless App running `GameplayPlugin` and `InputPlugin` on top of `MinimalPlugins`.
+pub struct HeadlessGame {
+    pub app: App,
+}
+
+impl HeadlessGame {
+    pub fn new() -> Self {
+        Self::with_settings(GameSettings::default())
+    }
+
+    pub fn with_settings(mut settings: GameSettings) -> Self {
+        settings.results_dir = std::env::temp_dir().join("stroop-tests");
+        // Kept out of the real data directory, tests that read it back pick their own.
+        settings
+            .data_dir
+            .get_or_insert_with(|| std::env::temp_dir().join("stroop-tests"));
+
+        let colors = ColorResource(
+            settings
+                .palette
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    (
+                        Handle::weak_from_u128(index as u128 + 1),
+                        entry.name.clone(),
+                        entry.color(),
+                    )
+                })
+                .collect(),
+        );
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::input::InputPlugin)
+            .add_state::<AppState>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .insert_resource(settings)
+            .insert_resource(ParticipantId("headless".to_string()))
+            .insert_resource(GameMode::Standard)
+            .insert_resource(colors)
+            .insert_resource(Locale::default())
+            .init_resource::<Fonts>()
+            .init_resource::<AudioVolumes>()
+            .insert_resource(MeshResource(Mesh2dHandle(Handle::default())))
+            .init_resource::<MissedCircles>()
+            .add_plugins(InputPlugin)
+            .add_plugins(GameplayPlugin)
+            .add_plugins(PausePlugin)
+            .add_plugins(StateScopePlugin)
+            .add_systems(Update, count_missed_circles);
+
+        app.world.spawn(Window {
+            resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
+            ..default()
+        });
+        app.world.spawn(Camera2dBundle::default());
+
+        HeadlessGame { app }
+    }
+
+    // Adds the menu screens, which the gameplay tests leave out.
+    pub fn with_menu(mut self) -> Self {
+        self.app.add_plugins(MenuPlugin);
+        self
+    }
+
+    // Adds the answer effects.
+    pub fn with_feedback(mut self) -> Self {
+        self.app.add_plugins(FeedbackPlugin);
+        self
+    }
+
+    // Adds the sound effects, without anything to play them. They're weak handles numbered from
+    // 1 in `AudioAssets` field order, the ticks last. There's no music unless a test sets some.
+    pub fn with_audio(mut self) -> Self {
+        let tone = |index: usize| Handle::weak_from_u128(index as u128 + 1);
+        self.app
+            .insert_resource(AudioAssets {
+                click: tone(0),
+                hit: tone(1),
+                miss: tone(2),
+                game_over: tone(3),
+                ticks: (0..COUNTDOWN_TICKS).map(|tick| tone(4 + tick)).collect(),
+                menu_music: None,
+                game_music: None,
+            })
+            .add_plugins(AudioPlugin);
+        self
+    }
+
+    // Adds the spoken words, without anything to play them. Voice `v` saying palette color `c` is
+    // weak handle `100 * (v + 1) + c`.
+    pub fn with_voices(mut self) -> Self {
+        let settings = self.app.world.resource::<GameSettings>();
+        let clips = VoiceClips(
+            (0..settings.voices.len())
+                .map(|voice| {
+                    (0..settings.palette.len())
+                        .map(|color| Handle::weak_from_u128((100 * (voice + 1) + color) as u128))
+                        .collect()
+                })
+                .collect(),
+        );
+        self.app
+            .add_plugins(AssetPlugin::default())
+            .insert_resource(clips)
+            .add_plugins(AuditoryPlugin);
+        self
+    }
+
+    // Adds the numbers on the circles for counting sessions.
+    pub fn with_counting(mut self) -> Self {
+        self.app.add_plugins(CountingPlugin);
+        self
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    // Runs as many frames as it takes to cover `duration`.
+    pub fn advance(&mut self, duration: Duration) {
+        let frames = (duration.as_secs_f64() / FRAME.as_secs_f64()).ceil() as usize;
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    // Leaves the menu and runs frames until the first round is on screen.
+    pub fn start_game(&mut self) {
+        self.app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::GameStart);
+        self.start_round();
+    }
+
+    // Runs frames until the next round is on screen, first waiting for the current one to end.
+    pub fn start_round(&mut self) {
+        let mut round_over = self.state() != AppState::InGame;
+        for _ in 0..10 {
+            self.update();
+            let in_game = self.state() == AppState::InGame;
+            if round_over && in_game {
+                return;
+            }
+            round_over |= !in_game;
+        }
+        panic!("game never reached AppState::InGame");
+    }
+
+    pub fn state(&self) -> AppState {
+        *self.app.world.resource::<State<AppState>>().get()
+    }
+
+    // Presses and releases `key` over two frames, as a real key press would arrive.
+    pub fn tap(&mut self, key: KeyCode) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world.send_event(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key),
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            self.update();
+        }
+    }
+
+    pub fn click(&mut self, position: Vec2) {
+        self.app.world.send_event(LeftClickEvent { position });
+    }
+
+    // Position of the first circle carrying `C`, e.g. `Correct` or `Incorrect`.
+    pub fn circle_position<C: Component>(&mut self) -> Vec2 {
+        self.app
+            .world
+            .query_filtered::<&Transform, With<C>>()
+            .iter(&self.app.world)
+            .next()
+            .expect("no circle on screen")
+            .translation
+            .truncate()
+    }
+
+    // How many entities carry `C`, circles or anything else.
+    pub fn count<C: Component>(&mut self) -> usize {
+        self.app
+            .world
+            .query_filtered::<(), With<C>>()
+            .iter(&self.app.world)
+            .count()
+    }
+
+    // The trial on screen, or the one just answered.
+    pub fn last_trial(&self) -> Trial {
+        self.app
+            .world
+            .resource::<TrialLog>()
+            .trials
+            .last()
+            .unwrap()
+            .clone()
+    }
+
+    // The sound effects playing right now.
+    pub fn sound_effects(&mut self) -> Vec<Handle<Pitch>> {
+        self.app
+            .world
+            .query_filtered::<&Handle<Pitch>, With<SoundEffect>>()
+            .iter(&self.app.world)
+            .cloned()
+            .collect()
+    }
+
+    pub fn game_state(&self) -> &GameState {
+        self.app.world.resource::<GameState>()
+    }
+
+    pub fn missed_circles(&self) -> usize {
+        self.app.world.resource::<MissedCircles>().0
+    }
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+fn correct_label(game: &mut HeadlessGame) -> String {
+    let children: Vec<Entity> = game
+        .app
+        .world
+        .query_filtered::<&Children, With<Correct>>()
+        .single(&game.app.world)
+        .to_vec();
+    let label = children
+        .into_iter()
+        .find(|child| game.app.world.get::<CountLabel>(*child).is_some())
+        .expect("correct circle has no number");
+    game.app.world.get::<Text>(label).unwrap().sections[0]
+        .value
+        .clone()
+}
+
+#[test]
+fn stimulus_repeats_a_digit() {
+    assert_eq!(counting_stimulus("3", 2), "3 3");
+    assert_eq!(counting_stimulus("#", 1), "#");
+}
+
+#[test]
+fn the_answer_is_how_many_digits_there_are() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Counting,
+        counting: CountingSettings {
+            neutral_ratio: 0.,
+            ..Default::default()
+        },
+        response_mode: ResponseMode::Mouse,
+        seed: Some(11),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_counting();
+    game.start_game();
+
+    for _ in 0..10 {
+        game.update();
+        let trial = game.last_trial();
+        let items: Vec<&str> = trial.word.split(' ').collect();
+        assert_eq!(trial.ink, items.len().to_string());
+        assert!(items.iter().all(|item| *item == items[0]));
+        assert_eq!(
+            items[0] == trial.ink,
+            trial.congruency == Congruency::Congruent
+        );
+        assert_eq!(trial.rule, COUNTING_RULE);
+        assert_eq!(correct_label(&mut game), trial.ink);
+
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+
+    assert_eq!(game.game_state().score(), 10);
+}
+
+#[test]
+fn number_keys_answer_with_the_count() {
+    let keys = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+    ];
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Counting,
+        counting: CountingSettings {
+            neutral_ratio: 0.,
+            ..Default::default()
+        },
+        response_mode: ResponseMode::Keyboard,
+        seed: Some(11),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_counting();
+    game.start_game();
+
+    for _ in 0..5 {
+        let count: usize = game.last_trial().ink.parse().unwrap();
+        game.tap(keys[count - 1]);
+        game.start_round();
+    }
+
+    assert_eq!(game.game_state().score(), 5);
+}
+
+#[test]
+fn neutral_trials_repeat_the_glyph() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Counting,
+        counting: CountingSettings {
+            neutral_ratio: 1.,
+            ..Default::default()
+        },
+        response_mode: ResponseMode::Mouse,
+        seed: Some(11),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_counting();
+    game.start_game();
+
+    let trial = game.last_trial();
+    assert_eq!(trial.congruency, Congruency::Neutral);
+    assert!(trial.word.split(' ').all(|item| item == "#"));
+}
+
+#[test]
+fn letter_keys_dont_answer_counts() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Counting,
+        counting: CountingSettings {
+            neutral_ratio: 0.,
+            ..Default::default()
+        },
+        response_mode: ResponseMode::Keyboard,
+        seed: Some(11),
+        target_speed: 0.,
+        ..Default::default()
+    })
+    .with_counting();
+    game.start_game();
+
+    for key in [KeyCode::R, KeyCode::Y, KeyCode::G, KeyCode::B, KeyCode::P] {
+        game.tap(key);
+    }
+
+    assert_eq!(game.last_trial().outcome, TrialOutcome::Unanswered);
+    assert_eq!(game.game_state().lives(), GameSettings::default().lives);
+    // The numbers on the circles are the only labels.
+    assert_eq!(game.count::<KeyLabel>(), 0);
+}
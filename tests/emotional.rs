@@ -0,0 +1,139 @@
+mod common;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use rand::prelude::*;
+use stroop::*;
+
+#[test]
+fn emotional_words_are_answered_by_their_ink() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Emotional,
+        seed: Some(5),
+        lives: 3,
+        target_speed: 0.,
+        ..Default::default()
+    });
+    game.start_game();
+    let words = EmotionalWords::default();
+
+    for _ in 0..10 {
+        let trial = game.last_trial();
+        let category = words
+            .categories
+            .iter()
+            .find(|category| Some(&category.name) == trial.category.as_ref())
+            .expect("trial has no word list");
+        assert!(category.words.contains(&trial.word));
+        assert_eq!(trial.congruency, Congruency::Neutral);
+        assert_eq!(trial.rule, WordOrColor::Color.label());
+
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+
+    assert_eq!(game.game_state().score(), 10);
+}
+
+#[test]
+fn session_results_are_split_by_category() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        stimulus: Stimulus::Emotional,
+        seed: Some(5),
+        lives: 1,
+        target_speed: 0.,
+        ..Default::default()
+    });
+    game.start_game();
+    for _ in 0..5 {
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.update();
+    game.update();
+    assert_eq!(game.state(), AppState::GameOver);
+
+    let results = game.app.world.resource::<SessionResults>();
+    let names: Vec<&str> = results
+        .categories
+        .iter()
+        .map(|summary| summary.category.as_str())
+        .collect();
+    assert_eq!(names, vec!["neutral", "negative", "positive"]);
+    let trials: usize = results
+        .categories
+        .iter()
+        .map(|summary| summary.trials)
+        .sum();
+    let correct: usize = results
+        .categories
+        .iter()
+        .map(|summary| summary.correct)
+        .sum();
+    assert_eq!(trials, 6);
+    assert_eq!(correct, 5);
+    assert_eq!(results.categories[0].bias_ms, None);
+    assert_eq!(results.stroop_effect_ms, None);
+}
+
+#[test]
+fn bias_is_measured_against_the_neutral_words() {
+    let trial = |category: &str, reaction_time: f64| {
+        let mut trial = Trial {
+            category: Some(category.to_string()),
+            ..Trial::new(
+                "WORD".to_string(),
+                "RED".to_string(),
+                WordOrColor::Color.label().to_string(),
+                Congruency::Neutral,
+                200.,
+                0.,
+            )
+        };
+        trial.resolve(TrialOutcome::Correct, reaction_time);
+        trial
+    };
+    let mut wrong = trial("negative", 0.1);
+    wrong.outcome = TrialOutcome::Wrong;
+    let log = TrialLog {
+        trials: vec![
+            trial("neutral", 0.5),
+            trial("neutral", 0.7),
+            trial("negative", 0.8),
+            wrong,
+        ],
+    };
+
+    let summaries = CategorySummary::all(&log, &EmotionalWords::default());
+
+    assert_eq!(summaries[0].mean_rt_ms.map(f64::round), Some(600.));
+    assert_eq!(summaries[1].trials, 2);
+    assert_eq!(summaries[1].correct, 1);
+    assert_eq!(summaries[1].bias_ms.map(f64::round), Some(200.));
+    // Nothing to measure without any answers.
+    assert_eq!(summaries[2].bias_ms, None);
+}
+
+#[test]
+fn emotional_trials_print_a_listed_word_in_the_answer_ink() {
+    let words = EmotionalWords::default();
+    let correct: Target = (Handle::default(), "GREEN".to_string(), Color::GREEN);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..20 {
+        let trial = emotional_trial(&words, &correct, &mut rng);
+        let category = words
+            .categories
+            .iter()
+            .find(|category| Some(&category.name) == trial.category.as_ref())
+            .unwrap();
+        assert!(category.words.contains(&trial.word));
+        assert_eq!(trial.text, trial.word);
+        assert_eq!((trial.ink.as_str(), trial.color), ("GREEN", Color::GREEN));
+        assert_eq!(trial.congruency, Congruency::Neutral);
+    }
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use std::time::Duration;
+
+use common::HeadlessGame;
+use stroop::*;
+
+#[test]
+fn correct_click_bursts_and_pops_up() {
+    let mut game = HeadlessGame::new().with_feedback();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.update();
+    game.update();
+
+    assert!(game.count::<HitParticle>() > 0);
+    assert_eq!(game.count::<ScorePopup>(), 1);
+
+    // The effects only last a moment.
+    game.advance(Duration::from_secs(1));
+    assert_eq!(game.count::<HitParticle>(), 0);
+    assert_eq!(game.count::<ScorePopup>(), 0);
+}
+
+#[test]
+fn wrong_click_flashes_the_wrong_circle() {
+    let mut game = HeadlessGame::new().with_feedback();
+    game.start_game();
+
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.update();
+    game.update();
+
+    assert_eq!(game.count::<WrongFlash>(), 1);
+    assert_eq!(game.count::<HitParticle>(), 0);
+    assert_eq!(game.count::<ScorePopup>(), 0);
+}
+
+#[test]
+fn each_effect_can_be_switched_off() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        feedback: FeedbackSettings {
+            hit_burst: false,
+            wrong_flash: false,
+            score_popup: false,
+            sounds: false,
+        },
+        ..Default::default()
+    })
+    .with_feedback();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.update();
+    game.update();
+
+    assert_eq!(game.game_state().score(), 1);
+    assert_eq!(game.count::<HitParticle>(), 0);
+    assert_eq!(game.count::<ScorePopup>(), 0);
+}
+
+#[test]
+fn answers_click_then_hit_or_miss() {
+    let mut game = HeadlessGame::new().with_feedback().with_audio();
+    game.start_game();
+    let audio = game.app.world.resource::<AudioAssets>().clone();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.update();
+    game.update();
+    assert_eq!(
+        game.sound_effects(),
+        [audio.click.clone(), audio.hit.clone()]
+    );
+
+    game.start_round();
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.update();
+    game.update();
+    let sounds = game.sound_effects();
+    assert_eq!(
+        sounds.iter().filter(|sound| **sound == audio.click).count(),
+        2
+    );
+    assert!(sounds.contains(&audio.miss));
+}
+
+#[test]
+fn answer_sounds_follow_the_feedback_setting() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        feedback: FeedbackSettings {
+            sounds: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .with_feedback()
+    .with_audio();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.update();
+    game.update();
+
+    assert!(game.sound_effects().is_empty());
+}
@@ -0,0 +1,495 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+#[test]
+fn correct_click_scores_and_starts_next_round() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.update();
+    assert_eq!(game.game_state().score(), 1);
+
+    game.update();
+    assert_eq!(game.state(), AppState::NextRound);
+}
+
+#[test]
+fn wrong_click_costs_a_life_and_continues() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.start_round();
+
+    assert_eq!(game.state(), AppState::InGame);
+    assert_eq!(game.game_state().lives(), GameSettings::default().lives - 1);
+}
+
+#[test]
+fn wrong_click_on_last_life_ends_game() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        lives: 1,
+        ..Default::default()
+    });
+    game.start_game();
+
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.update();
+    game.update();
+
+    assert_eq!(game.state(), AppState::GameOver);
+    assert_eq!(game.game_state().score(), 0);
+    assert!(!game.game_state().timer_expired());
+}
+
+#[test]
+fn only_the_first_response_in_a_frame_counts() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.app.world.send_event(MissedCircleEvent);
+    game.click(position);
+    game.update();
+
+    let lives = GameSettings::default().lives;
+    assert_eq!(game.game_state().lives(), lives - 1);
+    assert_eq!(game.game_state().score(), 0);
+    let resolved = game.app.world.resource::<Events<TrialResolvedEvent>>();
+    assert_eq!(resolved.len(), 1);
+    let log = game.app.world.resource::<TrialLog>();
+    assert_eq!(log.trials.last().unwrap().outcome, TrialOutcome::Missed);
+
+    // The click was dropped with the miss rather than carried over to the next round.
+    game.start_round();
+    assert_eq!(game.game_state().lives(), lives - 1);
+    assert_eq!(game.game_state().score(), 0);
+}
+
+#[test]
+fn missed_circle_on_last_life_sends_event_and_ends_game() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        lives: 1,
+        ..Default::default()
+    });
+    game.start_game();
+
+    // At the default 200 px/s the circles cross the 800 px window in well under five seconds.
+    game.advance(Duration::from_secs(5));
+
+    assert!(game.missed_circles() > 0);
+    assert_eq!(game.state(), AppState::GameOver);
+}
+
+#[test]
+fn timer_expiry_sets_timer_expired() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        round_seconds: 1.,
+        target_speed: 0.,
+        ..Default::default()
+    });
+    game.start_game();
+
+    game.advance(Duration::from_millis(1100));
+
+    assert!(game.game_state().timer_expired());
+    assert_eq!(game.state(), AppState::GameOver);
+}
+
+#[test]
+fn same_seed_presents_same_trials() {
+    let trials = || {
+        let mut game = HeadlessGame::with_settings(GameSettings {
+            seed: Some(7),
+            ..Default::default()
+        });
+        game.start_game();
+        for _ in 0..5 {
+            let position = game.circle_position::<Correct>();
+            game.click(position);
+            game.start_round();
+        }
+        game.app
+            .world
+            .resource::<TrialLog>()
+            .trials
+            .iter()
+            .map(|trial| (trial.word.clone(), trial.ink.clone(), trial.rule.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(trials(), trials());
+}
+
+#[test]
+fn difficulty_adds_distractors_as_score_rises() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        difficulty: DifficultySettings {
+            standard: DifficultyCurve::Linear { per_point: 5. },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    game.start_game();
+    assert_eq!(game.count::<Incorrect>(), 1);
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.start_round();
+
+    assert_eq!(game.app.world.resource::<Difficulty>().level, 5.);
+    assert_eq!(game.count::<Incorrect>(), 2);
+}
+
+fn hud_shows_lives(game: &mut HeadlessGame) -> bool {
+    game.app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .any(|text| text.sections[0].value.starts_with("Lives"))
+}
+
+#[test]
+fn hud_shows_lives_except_in_training() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+    game.update();
+    assert!(hud_shows_lives(&mut game));
+
+    let mut game = HeadlessGame::new();
+    game.app.world.insert_resource(GameMode::Training);
+    game.start_game();
+    game.update();
+    assert!(!hud_shows_lives(&mut game));
+}
+
+#[test]
+fn adaptive_difficulty_drops_after_wrong_answers_and_misses() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        difficulty: DifficultySettings {
+            standard: DifficultyCurve::Adaptive {
+                target_reaction_ms: 60_000.,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    game.start_game();
+    let level = |game: &HeadlessGame| game.app.world.resource::<Difficulty>().level;
+
+    for _ in 0..2 {
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+    assert_eq!(level(&game), 2.);
+
+    // Quicker than the target, but wrong.
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.start_round();
+    assert_eq!(level(&game), 1.);
+
+    game.app.world.send_event(MissedCircleEvent);
+    game.start_round();
+    assert_eq!(level(&game), 0.);
+}
+
+#[test]
+fn training_wrong_click_continues_and_slows_targets() {
+    let mut game = HeadlessGame::new();
+    game.app.world.insert_resource(GameMode::Training);
+    game.start_game();
+
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.start_round();
+
+    let settings = StaircaseSettings::default();
+    assert_eq!(game.state(), AppState::InGame);
+    assert_eq!(
+        game.app.world.resource::<Difficulty>().target_speed,
+        settings.start_speed - settings.step
+    );
+}
+
+#[test]
+fn pause_freezes_targets_and_timer() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.state(), AppState::Paused);
+    let position = game.circle_position::<Correct>();
+    let remaining = game.game_state().remaining_time();
+
+    game.advance(Duration::from_secs(10));
+    assert_eq!(game.circle_position::<Correct>(), position);
+    assert_eq!(game.game_state().remaining_time(), remaining);
+
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.state(), AppState::InGame);
+}
+
+#[test]
+fn quitting_from_pause_cleans_up_the_round() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    game.tap(KeyCode::Escape);
+    game.tap(KeyCode::Q);
+
+    assert_eq!(game.state(), AppState::Menu);
+    assert_eq!(game.count::<Correct>(), 0);
+    assert_eq!(game.count::<Incorrect>(), 0);
+    assert_eq!(game.count::<Text>(), 0);
+}
+
+#[test]
+fn next_round_replaces_the_targets() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.start_round();
+
+    assert_eq!(game.state(), AppState::InGame);
+    assert_eq!(game.count::<Correct>(), 1);
+    assert_eq!(game.count::<Incorrect>(), 1);
+}
+
+#[test]
+fn game_over_clears_the_round() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        round_seconds: 1.,
+        target_speed: 0.,
+        ..Default::default()
+    });
+    game.start_game();
+
+    game.advance(Duration::from_millis(1100));
+
+    assert_eq!(game.state(), AppState::GameOver);
+    assert_eq!(game.count::<Correct>(), 0);
+    assert_eq!(game.count::<Incorrect>(), 0);
+    // Only the game over message is left.
+    assert_eq!(game.count::<Text>(), 1);
+}
+
+#[test]
+fn restarting_from_pause_starts_a_fresh_round() {
+    let mut game = HeadlessGame::new();
+    game.start_game();
+    let hud_texts = game.count::<Text>();
+
+    game.tap(KeyCode::Escape);
+    game.tap(KeyCode::R);
+    game.start_round();
+
+    assert_eq!(game.state(), AppState::InGame);
+    assert_eq!(game.game_state().score(), 0);
+    assert_eq!(game.count::<Text>(), hud_texts);
+    assert_eq!(game.count::<Correct>(), 1);
+}
+
+#[test]
+fn practice_rounds_are_untimed_and_never_score() {
+    let mut game = HeadlessGame::new();
+    game.app.world.insert_resource(Practice::new(2));
+    game.start_game();
+
+    let remaining = game.game_state().remaining_time();
+    game.advance(Duration::from_millis(500));
+    assert_eq!(game.game_state().remaining_time(), remaining);
+
+    let position = game.circle_position::<Correct>();
+    game.click(position);
+    game.start_round();
+    assert_eq!(game.game_state().score(), 0);
+    assert_eq!(game.app.world.resource::<Practice>().completed, 1);
+
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.start_round();
+
+    // The practice block is over and the real game has started from scratch.
+    assert!(game.app.world.get_resource::<Practice>().is_none());
+    assert_eq!(game.game_state().lives(), GameSettings::default().lives);
+    assert_eq!(game.app.world.resource::<TrialLog>().trials.len(), 1);
+}
+
+// Plays 40 correct rounds in `game_mode`, checking each answer follows the rule, and returns
+// how many rule and congruency combinations came up.
+fn rule_and_congruency_combinations(game_mode: GameMode) -> usize {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        seed: Some(7),
+        target_speed: 0.,
+        ..Default::default()
+    });
+    game.app.world.insert_resource(game_mode);
+    game.start_game();
+
+    let mut combinations = Vec::new();
+    for _ in 0..40 {
+        let trial = game
+            .app
+            .world
+            .resource::<TrialLog>()
+            .trials
+            .last()
+            .unwrap()
+            .clone();
+        let answer = if trial.rule == WordOrColor::Word.label() {
+            &trial.word
+        } else {
+            &trial.ink
+        };
+        let correct_material = game
+            .app
+            .world
+            .query_filtered::<&Handle<ColorMaterial>, With<Correct>>()
+            .single(&game.app.world)
+            .clone();
+        let colors = game.app.world.resource::<ColorResource>();
+        let (_, correct_name, _) = colors
+            .iter()
+            .find(|(material, _, _)| *material == correct_material)
+            .unwrap();
+        assert_eq!(answer, correct_name);
+
+        if !combinations.contains(&(trial.rule.clone(), trial.congruency)) {
+            combinations.push((trial.rule, trial.congruency));
+        }
+
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+
+    combinations.len()
+}
+
+#[test]
+fn rule_and_congruency_vary_independently() {
+    let research = GameMode::Research {
+        congruent_ratio: 0.5,
+    };
+    assert_eq!(rule_and_congruency_combinations(research), 4);
+}
+
+#[test]
+fn standard_and_training_mix_in_congruent_trials() {
+    assert_eq!(rule_and_congruency_combinations(GameMode::Standard), 4);
+    assert_eq!(rule_and_congruency_combinations(GameMode::Training), 4);
+}
+
+fn palette(names: &[&str]) -> Vec<PaletteColor> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| PaletteColor {
+            name: name.to_string(),
+            rgb: (index as f32 / names.len() as f32, 0.5, 0.5),
+            key: None,
+        })
+        .collect()
+}
+
+#[test]
+fn two_color_palette_plays_with_distractors() {
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        palette: palette(&["BLACK", "WHITE"]),
+        difficulty: DifficultySettings {
+            distractor_step: 1.,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    game.start_game();
+
+    for _ in 0..3 {
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+
+    assert_eq!(game.game_state().score(), 3);
+    assert_eq!(game.count::<Incorrect>(), 4);
+}
+
+#[test]
+fn large_palette_answers_with_configured_keys() {
+    let names = [
+        "RED", "ORANGE", "YELLOW", "GREEN", "CYAN", "BLUE", "VIOLET", "PINK", "BROWN", "GREY",
+    ];
+    let mut colors = palette(&names);
+    for (color, key) in colors.iter_mut().zip("ABCDEFGHIJ".chars()) {
+        color.key = Some(key);
+    }
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        palette: colors,
+        response_mode: ResponseMode::Keyboard,
+        ..Default::default()
+    });
+    game.start_game();
+
+    // The harness gives each palette entry a weak material handle numbered from 1.
+    let correct_material = game
+        .app
+        .world
+        .query_filtered::<&Handle<ColorMaterial>, With<Correct>>()
+        .single(&game.app.world)
+        .clone();
+    let index = (0..names.len())
+        .find(|index| correct_material == Handle::weak_from_u128(*index as u128 + 1))
+        .unwrap();
+    let key = [
+        KeyCode::A,
+        KeyCode::B,
+        KeyCode::C,
+        KeyCode::D,
+        KeyCode::E,
+        KeyCode::F,
+        KeyCode::G,
+        KeyCode::H,
+        KeyCode::I,
+        KeyCode::J,
+    ][index];
+    game.tap(key);
+
+    assert_eq!(game.game_state().score(), 1);
+}
+
+#[test]
+fn stimulus_word_uses_its_own_font() {
+    let mut game = HeadlessGame::new();
+    let stimulus = Handle::weak_from_u128(1);
+    game.app.world.insert_resource(Fonts {
+        stimulus: stimulus.clone(),
+        stimulus_size: 120.,
+        ..Default::default()
+    });
+    game.start_game();
+
+    let stimulus_sections: Vec<TextSection> = game
+        .app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .flat_map(|text| text.sections.clone())
+        .filter(|section| section.style.font == stimulus)
+        .collect();
+    assert_eq!(stimulus_sections.len(), 1);
+    assert_eq!(stimulus_sections[0].style.font_size, 120.);
+    assert!(!stimulus_sections[0].value.is_empty());
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use std::fs;
+
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+fn entry(name: &str, score: usize) -> LeaderboardEntry {
+    LeaderboardEntry {
+        name: name.to_string(),
+        score,
+        date: "2024-01-01".to_string(),
+        mode: "Standard".to_string(),
+    }
+}
+
+#[test]
+fn insert_keeps_highest_scores_in_order() {
+    let mut leaderboard = Leaderboard::default();
+    leaderboard.insert(entry("a", 5), 3);
+    leaderboard.insert(entry("b", 9), 3);
+    leaderboard.insert(entry("c", 5), 3);
+    leaderboard.insert(entry("d", 1), 3);
+
+    let names: Vec<&str> = leaderboard
+        .entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect();
+    assert_eq!(names, ["b", "a", "c"]);
+}
+
+#[test]
+fn qualifies_only_when_it_would_make_the_table() {
+    let mut leaderboard = Leaderboard::default();
+    assert!(!leaderboard.qualifies(0, 2));
+    assert!(leaderboard.qualifies(1, 2));
+
+    leaderboard.insert(entry("a", 5), 2);
+    leaderboard.insert(entry("b", 3), 2);
+    assert!(!leaderboard.qualifies(3, 2));
+    assert!(leaderboard.qualifies(4, 2));
+}
+
+// Scores one point and then loses the only life, with the leaderboard kept in `dir`.
+fn finished_game(dir: &str, score: usize) -> HeadlessGame {
+    let data_dir = std::env::temp_dir().join("stroop-tests").join(dir);
+    let _ = fs::remove_dir_all(&data_dir);
+    let mut game = HeadlessGame::with_settings(GameSettings {
+        lives: 1,
+        target_speed: 0.,
+        data_dir: Some(data_dir),
+        ..Default::default()
+    });
+    // Typed characters come from the window plugin, which the headless game leaves out.
+    game.app
+        .add_event::<ReceivedCharacter>()
+        .add_plugins(LeaderboardPlugin);
+    game.start_game();
+    for _ in 0..score {
+        let position = game.circle_position::<Correct>();
+        game.click(position);
+        game.start_round();
+    }
+    let position = game.circle_position::<Incorrect>();
+    game.click(position);
+    game.update();
+    game.update();
+    assert_eq!(game.state(), AppState::GameOver);
+    game
+}
+
+#[test]
+fn high_score_is_named_and_shown_on_the_leaderboard() {
+    let mut game = finished_game("leaderboard-flow", 2);
+
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::NameEntry);
+
+    for char in "ADA".chars() {
+        game.app.world.send_event(ReceivedCharacter {
+            window: Entity::PLACEHOLDER,
+            char,
+        });
+    }
+    game.update();
+    game.tap(KeyCode::Return);
+    game.update();
+    assert_eq!(game.state(), AppState::Leaderboard);
+
+    let leaderboard = game.app.world.resource::<Leaderboard>();
+    assert_eq!(leaderboard.entries.len(), 1);
+    assert_eq!(leaderboard.entries[0].name, "ADA");
+    assert_eq!(leaderboard.entries[0].score, 2);
+
+    let settings = game.app.world.resource::<GameSettings>();
+    let saved = Leaderboard::load(&settings.data_dir());
+    assert_eq!(saved.entries[0].name, "ADA");
+}
+
+#[test]
+fn enter_restarts_when_there_is_no_high_score() {
+    let mut game = finished_game("leaderboard-no-score", 0);
+
+    game.tap(KeyCode::Return);
+    game.update();
+
+    assert_ne!(game.state(), AppState::GameOver);
+    assert_ne!(game.state(), AppState::NameEntry);
+    assert_eq!(game.game_state().score(), 0);
+}
@@ -0,0 +1,71 @@
+use std::path::Path;
+
+use stroop::*;
+
+#[test]
+fn missing_strings_fall_back_to_english() {
+    let locale = Locale::default();
+
+    assert_eq!(locale.get("pause.title"), "Paused");
+    assert_eq!(locale.color_name("RED"), "RED");
+    // Unknown keys and palette colors without a translation are shown as they are.
+    assert_eq!(locale.get("no.such.key"), "no.such.key");
+    assert_eq!(locale.stimulus_word("TEAL"), "TEAL");
+}
+
+#[test]
+fn stimulus_language_is_independent_of_the_ui() {
+    let locale = Locale::new(Path::new(LOCALES_DIR), "en", "de").unwrap();
+
+    assert_eq!(locale.get("pause.title"), "Paused");
+    assert_eq!(locale.color_name("RED"), "RED");
+    assert_eq!(locale.stimulus_word("RED"), "ROT");
+}
+
+#[test]
+fn format_fills_in_placeholders() {
+    let locale = Locale::default();
+
+    assert_eq!(
+        locale.format("settings.lives", &[("lives", &3)]),
+        "Lives: 3"
+    );
+}
+
+#[test]
+fn unknown_language_is_an_error() {
+    assert!(Locale::new(Path::new(LOCALES_DIR), "xx", "en").is_err());
+}
+
+#[test]
+fn shipped_translations_cover_every_english_string() {
+    let english = StringTable::english();
+    for language in ["es", "de"] {
+        let table = StringTable::load(Path::new(LOCALES_DIR), language).unwrap();
+        for key in english.keys() {
+            assert!(table.get(key).is_some(), "{} is missing {}", language, key);
+        }
+        for key in table.keys() {
+            assert!(
+                english.get(key).is_some(),
+                "{} has unknown {}",
+                language,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn answer_letters_follow_the_ui_language() {
+    let settings = GameSettings::default();
+
+    let english = answer_letters(&settings, &Locale::default());
+    assert_eq!(english, ['R', 'Y', 'G', 'B', 'P'].map(Some));
+
+    // GELB and GRÜN both start with G and ROT has R, so GRÜN gets its next free letter.
+    let german = Locale::new(Path::new(LOCALES_DIR), "de", "de").unwrap();
+    let letters = answer_letters(&settings, &german);
+    assert_eq!(letters[1], Some('G'));
+    assert_eq!(letters[2], Some('N'));
+}
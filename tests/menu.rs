@@ -0,0 +1,210 @@
+mod common;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use common::HeadlessGame;
+use stroop::*;
+
+#[test]
+fn main_menu_lists_every_entry() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    assert_eq!(game.count::<MenuButton>(), 6);
+    assert_eq!(game.count::<Menu>(), 1);
+}
+
+#[test]
+fn play_shows_the_instructions_before_the_game() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    assert_eq!(game.count::<Instructions>(), 1);
+    assert_eq!(game.count::<Menu>(), 0);
+
+    // Practice, then Start Game.
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    game.update();
+
+    assert_ne!(game.state(), AppState::Instructions);
+    assert!(game.app.world.get_resource::<Practice>().is_none());
+}
+
+fn instructions_text(game: &mut HeadlessGame) -> String {
+    game.app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .flat_map(|text| text.sections.iter().map(|section| section.value.clone()))
+        .collect()
+}
+
+#[test]
+fn play_and_instructions_part_ways_once_the_instructions_are_seen() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    // Play, then Back from the instructions.
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.state(), AppState::Menu);
+
+    // Instructions is fifth.
+    for _ in 0..4 {
+        game.tap(KeyCode::Down);
+    }
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    game.tap(KeyCode::Escape);
+
+    game.tap(KeyCode::Return);
+    game.update();
+    assert!(!matches!(
+        game.state(),
+        AppState::Menu | AppState::Instructions
+    ));
+    assert!(game.app.world.get_resource::<Practice>().is_none());
+}
+
+#[test]
+fn training_instructions_say_errors_cost_no_lives() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+    game.tap(KeyCode::Return);
+    let locale = Locale::default();
+    assert!(instructions_text(&mut game).contains(&locale.get("instructions.errors")));
+
+    game.tap(KeyCode::Escape);
+    game.app.insert_resource(GameMode::Training);
+    game.update();
+    // Play would start the game now, the instructions have their own entry.
+    for _ in 0..4 {
+        game.tap(KeyCode::Down);
+    }
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    let text = instructions_text(&mut game);
+    assert!(text.contains(&locale.get("instructions.errors_training")));
+    assert!(!text.contains(&locale.get("instructions.errors")));
+}
+
+#[test]
+fn practice_entry_starts_a_practice_block() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Return);
+    game.tap(KeyCode::Return);
+    game.update();
+
+    let practice = game.app.world.resource::<Practice>();
+    assert_eq!(practice.trials, GameSettings::default().practice_trials);
+    assert_eq!(practice.completed, 0);
+}
+
+#[test]
+fn mode_entry_cycles_through_the_modes() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    assert!(matches!(
+        *game.app.world.resource::<GameMode>(),
+        GameMode::Research { .. }
+    ));
+
+    game.tap(KeyCode::Return);
+    assert_eq!(*game.app.world.resource::<GameMode>(), GameMode::Training);
+    assert_eq!(game.state(), AppState::Menu);
+}
+
+#[test]
+fn settings_change_lives_and_go_back() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Settings);
+
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    assert_eq!(
+        game.app.world.resource::<GameSettings>().lives,
+        GameSettings::default().lives + 1
+    );
+
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.state(), AppState::Menu);
+    assert_eq!(game.count::<MenuButton>(), 6);
+}
+
+#[test]
+fn settings_cycle_the_volumes() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+
+    // Master volume, then music volume twice.
+    for _ in 0..3 {
+        game.tap(KeyCode::Down);
+    }
+    game.tap(KeyCode::Return);
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    game.tap(KeyCode::Return);
+
+    let volumes = game.app.world.resource::<AudioVolumes>();
+    assert_eq!(volumes.master, 0.);
+    assert_eq!(volumes.music, 1.);
+    assert_eq!(volumes.sfx, AudioVolumes::default().sfx);
+}
+
+#[test]
+fn selection_wraps_around_to_quit() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.update();
+
+    game.tap(KeyCode::Up);
+    game.tap(KeyCode::Return);
+
+    assert!(!game.app.world.resource::<Events<AppExit>>().is_empty());
+}
+
+#[test]
+fn invalid_settings_are_shown_and_refuse_to_start() {
+    let mut game = HeadlessGame::new().with_menu();
+    game.app.insert_resource(InvalidSettings(
+        "Invalid settings in settings.ron".to_string(),
+    ));
+    game.update();
+
+    let shown = game
+        .app
+        .world
+        .query::<&Text>()
+        .iter(&game.app.world)
+        .any(|text| {
+            text.sections[0]
+                .value
+                .contains("Invalid settings in settings.ron")
+        });
+    assert!(shown);
+
+    game.tap(KeyCode::Return);
+    assert_eq!(game.state(), AppState::Instructions);
+    game.tap(KeyCode::Return);
+    game.tap(KeyCode::Down);
+    game.tap(KeyCode::Return);
+    game.update();
+    assert_eq!(game.state(), AppState::Instructions);
+}
@@ -0,0 +1,98 @@
+use std::fs;
+
+use stroop::*;
+
+fn answered(congruency: Congruency, outcome: TrialOutcome, seconds: f64) -> Trial {
+    let mut trial = Trial::new(
+        "RED".to_string(),
+        "BLUE".to_string(),
+        WordOrColor::Color.label().to_string(),
+        congruency,
+        200.,
+        1.,
+    );
+    trial.resolve(outcome, 1. + seconds);
+    trial
+}
+
+#[test]
+fn export_writes_csv_and_json_inside_the_results_dir() {
+    let dir = std::env::temp_dir().join("stroop-tests").join("export");
+    let _ = fs::remove_dir_all(&dir);
+    let log = TrialLog {
+        trials: vec![
+            answered(Congruency::Congruent, TrialOutcome::Correct, 0.5),
+            answered(Congruency::Incongruent, TrialOutcome::Wrong, 0.7),
+        ],
+    };
+    let results = SessionResults::new(
+        &ParticipantId("../../P01".to_string()),
+        "Research".to_string(),
+        Stimulus::Visual,
+        "en".to_string(),
+        42,
+        1,
+        true,
+        &log,
+        None,
+        Vec::new(),
+    );
+
+    let (csv_path, json_path) = results.export(&dir).unwrap();
+
+    assert_eq!(csv_path.parent(), Some(dir.as_path()));
+    assert_eq!(json_path.parent(), Some(dir.as_path()));
+    assert!(csv_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("______P01_"));
+
+    let mut reader = csv::Reader::from_path(&csv_path).unwrap();
+    let headers = reader.headers().unwrap().clone();
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 2);
+    let column = |row: &csv::StringRecord, name: &str| {
+        row[headers.iter().position(|header| header == name).unwrap()].to_string()
+    };
+    assert_eq!(column(&rows[0], "participant_id"), "../../P01");
+    assert_eq!(column(&rows[0], "trial"), "1");
+    assert_eq!(column(&rows[0], "congruency"), "Congruent");
+    assert_eq!(column(&rows[1], "outcome"), "Wrong");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(json["seed"], 42);
+    assert_eq!(json["trials"].as_array().unwrap().len(), 2);
+    assert_eq!(json["trials"][1]["outcome"], "Wrong");
+}
+
+#[test]
+fn stroop_effect_is_incongruent_minus_congruent_reaction_time() {
+    let log = TrialLog {
+        trials: vec![
+            answered(Congruency::Congruent, TrialOutcome::Correct, 0.4),
+            answered(Congruency::Congruent, TrialOutcome::Correct, 0.6),
+            answered(Congruency::Incongruent, TrialOutcome::Correct, 0.7),
+            answered(Congruency::Incongruent, TrialOutcome::Correct, 0.9),
+            // Errors don't count toward either mean.
+            answered(Congruency::Incongruent, TrialOutcome::Wrong, 5.),
+        ],
+    };
+
+    let round = |ms: Option<f64>| ms.map(f64::round);
+    assert_eq!(
+        round(log.mean_reaction_time_ms(Congruency::Congruent)),
+        Some(500.)
+    );
+    assert_eq!(
+        round(log.mean_reaction_time_ms(Congruency::Incongruent)),
+        Some(800.)
+    );
+    assert_eq!(round(log.stroop_effect_ms()), Some(300.));
+
+    let congruent_only = TrialLog {
+        trials: log.trials[..2].to_vec(),
+    };
+    assert_eq!(congruent_only.stroop_effect_ms(), None);
+}
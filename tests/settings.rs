@@ -0,0 +1,162 @@
+use std::fs;
+use std::path::Path;
+
+use stroop::*;
+
+#[test]
+fn default_settings_are_valid() {
+    assert_eq!(GameSettings::default().validate(), Ok(()));
+}
+
+#[test]
+fn sizes_and_times_must_be_positive_numbers() {
+    let invalid = [
+        GameSettings {
+            round_seconds: -1.,
+            ..Default::default()
+        },
+        GameSettings {
+            round_seconds: f32::NAN,
+            ..Default::default()
+        },
+        GameSettings {
+            hit_radius: 0.,
+            ..Default::default()
+        },
+        GameSettings {
+            circle_radius: f32::INFINITY,
+            ..Default::default()
+        },
+        GameSettings {
+            target_speed: -200.,
+            ..Default::default()
+        },
+    ];
+    for settings in invalid {
+        assert!(settings.validate().is_err());
+    }
+
+    let still_targets = GameSettings {
+        target_speed: 0.,
+        ..Default::default()
+    };
+    assert_eq!(still_targets.validate(), Ok(()));
+}
+
+#[test]
+fn training_leaves_adapting_to_the_staircase() {
+    let mut settings = GameSettings::default();
+    settings.difficulty.training = DifficultyCurve::Adaptive {
+        target_reaction_ms: 600.,
+    };
+    assert!(settings.validate().is_err());
+
+    settings.difficulty.training = DifficultyCurve::Flat;
+    settings.difficulty.standard = DifficultyCurve::Adaptive {
+        target_reaction_ms: 600.,
+    };
+    assert_eq!(settings.validate(), Ok(()));
+}
+
+#[test]
+fn difficulty_knobs_must_keep_the_bands_on_screen() {
+    let tweaks: [fn(&mut DifficultySettings); 9] = [
+        |difficulty| difficulty.speed_step = -0.1,
+        |difficulty| difficulty.separation_step = -1.,
+        |difficulty| difficulty.distractor_step = f32::NAN,
+        |difficulty| difficulty.max_speed_multiplier = 0.5,
+        |difficulty| difficulty.min_separation = 0.,
+        |difficulty| difficulty.min_separation = 1.5,
+        |difficulty| difficulty.standard = DifficultyCurve::Linear { per_point: -1. },
+        |difficulty| {
+            difficulty.research = DifficultyCurve::Stepped {
+                points: 5,
+                levels: f32::INFINITY,
+            }
+        },
+        |difficulty| {
+            difficulty.standard = DifficultyCurve::Adaptive {
+                target_reaction_ms: 0.,
+            }
+        },
+    ];
+    for (index, tweak) in tweaks.iter().enumerate() {
+        let mut settings = GameSettings::default();
+        tweak(&mut settings.difficulty);
+        assert!(settings.validate().is_err(), "tweak {}", index);
+    }
+}
+
+fn staircase_is_invalid(tweak: impl Fn(&mut StaircaseSettings)) -> bool {
+    let mut settings = GameSettings::default();
+    tweak(&mut settings.staircase);
+    settings.validate().is_err()
+}
+
+#[test]
+fn staircase_speeds_must_be_ordered() {
+    assert!(staircase_is_invalid(|staircase| staircase.min_speed = -1.));
+    assert!(staircase_is_invalid(
+        |staircase| staircase.min_speed = f32::NAN
+    ));
+    assert!(staircase_is_invalid(
+        |staircase| staircase.max_speed = f32::INFINITY
+    ));
+    assert!(staircase_is_invalid(|staircase| {
+        staircase.min_speed = 900.;
+        staircase.start_speed = 900.;
+    }));
+}
+
+#[test]
+fn staircase_starts_within_its_bounds() {
+    assert!(staircase_is_invalid(|staircase| staircase.start_speed = 10.));
+    assert!(staircase_is_invalid(
+        |staircase| staircase.start_speed = 1000.
+    ));
+}
+
+#[test]
+fn staircase_step_must_be_positive() {
+    assert!(staircase_is_invalid(|staircase| staircase.step = 0.));
+    assert!(staircase_is_invalid(|staircase| staircase.step = f32::NAN));
+}
+
+#[test]
+fn staircase_runs_need_at_least_one_answer() {
+    assert!(staircase_is_invalid(|staircase| staircase.down = 0));
+    assert!(staircase_is_invalid(|staircase| staircase.up = 0));
+}
+
+#[test]
+fn counting_neutral_ratio_is_a_share() {
+    for neutral_ratio in [-0.1, 1.5, f64::NAN] {
+        let settings = GameSettings {
+            stimulus: Stimulus::Counting,
+            counting: CountingSettings {
+                neutral_ratio,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err(), "{}", neutral_ratio);
+    }
+}
+
+#[test]
+fn shipped_settings_use_bundled_fonts() {
+    let settings: GameSettings =
+        ron::from_str(&fs::read_to_string(SETTINGS_PATH).unwrap()).unwrap();
+
+    assert!(settings.fonts.ui.is_some());
+    for font in [&settings.fonts.ui, &settings.fonts.stimulus]
+        .into_iter()
+        .flatten()
+    {
+        assert!(
+            Path::new("assets").join(font).is_file(),
+            "{} is missing",
+            font
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use stroop::*;
+
+#[test]
+fn two_down_one_up_moves_speed_and_records_reversals() {
+    let settings = StaircaseSettings::default();
+    let mut staircase = Staircase::new(&settings);
+
+    staircase.record(true, &settings);
+    assert_eq!(staircase.speed, settings.start_speed);
+    staircase.record(true, &settings);
+    assert_eq!(staircase.speed, settings.start_speed + settings.step);
+
+    staircase.record(false, &settings);
+    assert_eq!(staircase.speed, settings.start_speed);
+    assert_eq!(staircase.reversals, [settings.start_speed + settings.step]);
+    assert!(staircase.track[2].reversal);
+    assert_eq!(staircase.track.len(), 3);
+
+    assert_eq!(
+        staircase.threshold(&settings),
+        Some(settings.start_speed + settings.step)
+    );
+}
+
+#[test]
+fn speed_stays_within_bounds() {
+    let settings = StaircaseSettings {
+        min_speed: 190.,
+        ..Default::default()
+    };
+    let mut staircase = Staircase::new(&settings);
+    for _ in 0..5 {
+        staircase.record(false, &settings);
+    }
+    assert_eq!(staircase.speed, 190.);
+    assert_eq!(staircase.threshold(&settings), None);
+}